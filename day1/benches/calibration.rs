@@ -0,0 +1,79 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day1::calibration::Calibrator;
+use day1::digits::{DigitRecognizer, NumericDigits, SpelledDigits};
+
+const WORDS: [&str; 14] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "x", "qz", "treb",
+    "uchet", "7",
+];
+
+// A reproducible document of long lines mixing digits, digit words and filler.
+fn document(lines: usize, words_per_line: usize) -> Vec<String> {
+    let mut seed: u64 = 2023;
+    let mut next = move || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) as usize
+    };
+
+    (0..lines)
+        .map(|_| {
+            (0..words_per_line)
+                .map(|_| WORDS[next() % WORDS.len()])
+                .collect()
+        })
+        .collect()
+}
+
+fn compare<R: DigitRecognizer>(c: &mut Criterion, name: &str, calibrator: &Calibrator<R>) {
+    let mut group = c.benchmark_group(name);
+
+    for words_per_line in [10, 100, 1000] {
+        let document = document(2000, words_per_line);
+        let bytes: usize = document.iter().map(|line| line.len()).sum();
+        group.throughput(Throughput::Bytes(bytes as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("all digits", words_per_line),
+            &document,
+            |b, document| b.iter(|| calibrator.calibrate_document(document, |_, _| {})),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("first and last", words_per_line),
+            &document,
+            |b, document| b.iter(|| calibrator.total(document)),
+        );
+    }
+
+    group.finish();
+}
+
+// Sequential totals against the same document split over several threads.
+fn parallel<R: DigitRecognizer + Sync>(c: &mut Criterion, name: &str, calibrator: &Calibrator<R>) {
+    let mut group = c.benchmark_group(name);
+    let document = document(20_000, 100).join("\n");
+    group.throughput(Throughput::Bytes(document.len() as u64));
+
+    group.bench_function("sequential", |b| {
+        b.iter(|| calibrator.total(document.lines()))
+    });
+    for threads in [2, 4, 8] {
+        group.bench_with_input(
+            BenchmarkId::new("parallel", threads),
+            &threads,
+            |b, &threads| b.iter(|| calibrator.total_parallel(&document, threads)),
+        );
+    }
+
+    group.finish();
+}
+
+fn calibration(c: &mut Criterion) {
+    compare(c, "numeric", &Calibrator::new(NumericDigits::default()));
+    compare(c, "spelled", &Calibrator::new(SpelledDigits::default()));
+    parallel(c, "parallel", &Calibrator::new(SpelledDigits::default()));
+}
+
+criterion_group!(benches, calibration);
+criterion_main!(benches);
@@ -1,110 +1,84 @@
-use fancy_regex::Regex;
-
-use std::collections::HashMap;
-
-fn parse_digit_text(text: &str) -> Option<&str> {
-    let mut digit_map = HashMap::new();
-
-    digit_map.insert("one", "1");
-    digit_map.insert("two", "2");
-    digit_map.insert("three", "3");
-    digit_map.insert("four", "4");
-    digit_map.insert("five", "5");
-    digit_map.insert("six", "6");
-    digit_map.insert("seven", "7");
-    digit_map.insert("eight", "8");
-    digit_map.insert("nine", "9");
-    digit_map.insert("zero", "0");
-
-    if text.chars().all(char::is_numeric) {
-        return Some(text);
-    }
-
-    digit_map.get(text).map(|&x| x)
+use clap::Args;
+use day1::calibration::Calibrator;
+use day1::cli::CalibrationArgs;
+use day1::digits::SpelledDigits;
+use day1::fuzzy::{FuzzyConfig, FuzzyDigits};
+use day1::phrases::{number_tokens, quantity_total};
+use day1::vocabulary::Vocabulary;
+use runner::{Input, Runner};
+
+use std::path::PathBuf;
+use std::process;
+
+#[derive(Args, Debug)]
+struct SpelledArgs {
+    /// Language of the spelled-out digits (english, spanish, german or french)
+    #[arg(long, default_value = "english")]
+    language: String,
+
+    /// Read the spelled-out digits from a file of `word = digit` lines instead
+    #[arg(long, value_name = "PATH", conflicts_with = "language")]
+    vocabulary: Option<PathBuf>,
+
+    /// Also read misspelled digits, up to this many edits away from a word
+    #[arg(long, value_name = "DISTANCE")]
+    fuzzy: Option<usize>,
+
+    /// Sum the written English quantities of the document ("one hundred and five") instead
+    #[arg(long, conflicts_with_all = ["fuzzy", "explain", "language", "vocabulary", "threads"])]
+    phrases: bool,
+
+    #[command(flatten)]
+    calibration: CalibrationArgs,
 }
 
-fn find_digits(line: &str) -> Vec<String> {
-    let mut digits = Vec::new();
-    let re = Regex::new(r#"(?=(\d|one|two|three|four|five|six|seven|eight|nine|zero))"#).unwrap();
-
-    for result in re.captures_iter(line) {
-        let captures = result.expect("Error running regex");
-        let digit = captures.get(1).unwrap().as_str();
-        let parsed_digit = parse_digit_text(digit).unwrap();
-        digits.push(parsed_digit.to_string());
-    }
-
-    digits
-}
-
-fn calc_calibration_value(line: &str) -> u8 {
-    let mut calibration_value: u8 = 0;
-    let digits = find_digits(line);
-
-    if digits.len() == 0 {
-        return calibration_value;
-    }
-
-    if digits.len() == 1 {
-        calibration_value = digits[0].repeat(2).parse::<u8>().unwrap();
-    } else {
-        let first_digit = &digits[0];
-        let last_digit = &digits[digits.len() - 1];
-        let calibration_str = format!("{first_digit}{last_digit}");
-        calibration_value = calibration_str.parse::<u8>().unwrap();
-    }
-    calibration_value
+fn sum_phrases(input: &mut Input) -> u64 {
+    let lines = input
+        .lines()
+        .map(|line| line.expect("Could not read puzzle input"))
+        .inspect(|line| {
+            let tokens = number_tokens(line).unwrap_or_default();
+            let values: Vec<String> = tokens
+                .iter()
+                .map(|token| format!("{:?} = {}", token.text, token.value))
+                .collect();
+            println!("[{}] <- {}", values.join(", "), line);
+        });
+
+    quantity_total(lines).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    })
 }
 
 fn main() {
-    let data = include_str!("../data.txt");
-
-    let mut sum: i32 = 0;
-
-    for line in data.lines() {
-        let digits = find_digits(&line);
-        let calibration_value = calc_calibration_value(&line);
-
-        println!("{} <- {:?} <- {}", calibration_value, digits.join(""), line);
-        sum += calibration_value as i32;
+    let (mut runner, args) = Runner::from_args_with::<SpelledArgs>();
+    let vocabulary = match &args.vocabulary {
+        Some(path) => Vocabulary::from_file(path),
+        None => Vocabulary::builtin(&args.language),
     }
-
-    println!("Final calibration value: {}", sum);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_digit_text() {
-        assert_eq!(parse_digit_text("1"), Some("1"));
-        assert_eq!(parse_digit_text("1234"), Some("1234"));
-        assert_eq!(parse_digit_text("one"), Some("1"));
-        assert_eq!(parse_digit_text("two"), Some("2"));
-        assert_eq!(parse_digit_text("notanumber"), None);
+    .unwrap_or_else(|err| {
+        eprintln!("Could not load vocabulary: {}", err);
+        process::exit(1);
+    });
+
+    if args.phrases {
+        let mut input = runner.open_input(env!("CARGO_MANIFEST_DIR"));
+        println!("Total quantity: {}", sum_phrases(&mut input));
+        return;
     }
 
-    #[test]
-    fn test_find_digits() {
-        assert_eq!(
-            find_digits("abc123def456"),
-            vec!["1", "2", "3", "4", "5", "6"]
-        );
-        assert_eq!(find_digits("no digits here"), vec![] as Vec<String>);
-        assert_eq!(find_digits("111"), vec!["1", "1", "1"]);
-        assert_eq!(find_digits("2"), vec!["2"]);
-        assert_eq!(find_digits("fourfive6seven"), vec!["4", "5", "6", "7"]);
-        assert_eq!(find_digits("zoneight234"), vec!["1", "8", "2", "3", "4"]);
-    }
+    let spelled = SpelledDigits::with_policy(vocabulary, args.calibration.unicode);
+    let sum = match args.fuzzy {
+        Some(distance) => {
+            let fuzzy = FuzzyDigits::new(spelled, FuzzyConfig::new(distance));
+            args.calibration
+                .calibrate(&mut runner, &Calibrator::new(fuzzy))
+        }
+        None => args
+            .calibration
+            .calibrate(&mut runner, &Calibrator::new(spelled)),
+    };
 
-    #[test]
-    fn test_calc_calibration_value() {
-        assert_eq!(calc_calibration_value("abc123def456"), 16);
-        assert_eq!(calc_calibration_value("no digits here"), 0);
-        assert_eq!(calc_calibration_value("111"), 11);
-        assert_eq!(calc_calibration_value("2"), 22);
-        assert_eq!(calc_calibration_value("abc1pqsttwo33four"), 14);
-        assert_eq!(calc_calibration_value("zoneight234"), 14);
-    }
+    println!("Final calibration value: {}", sum);
 }
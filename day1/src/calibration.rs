@@ -0,0 +1,507 @@
+use std::fmt;
+
+use crate::digits::DigitRecognizer;
+use crate::explain::Explanation;
+use crate::extraction::{Extraction, ExtractionError};
+use crate::unicode::UnicodeError;
+
+// The two digits a calibration line is reduced to. A line with a single digit
+// uses it as both the first and the last one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalibrationValue {
+    pub first: u8,
+    pub last: u8,
+}
+
+impl CalibrationValue {
+    pub fn from_digits(digits: &[u8]) -> Option<CalibrationValue> {
+        let first = *digits.first()?;
+        let last = *digits.last()?;
+
+        Some(CalibrationValue { first, last })
+    }
+
+    pub fn value(&self) -> u8 {
+        self.first * 10 + self.last
+    }
+}
+
+impl fmt::Display for CalibrationValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineCalibration {
+    pub digits: Vec<u8>,
+    pub value: Option<u64>,
+}
+
+impl LineCalibration {
+    // lines without a value don't add anything to the document
+    pub fn value(&self) -> u64 {
+        self.value.unwrap_or(0)
+    }
+}
+
+impl fmt::Display for LineCalibration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits: String = self.digits.iter().map(|digit| digit.to_string()).collect();
+        write!(f, "{} <- {:?}", self.value(), digits)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalibrationError {
+    TotalOverflow {
+        line_number: usize,
+        total: u64,
+        value: u64,
+    },
+    ValueOverflow {
+        line_number: usize,
+        digits: usize,
+    },
+    UnsupportedCharacters {
+        line_number: usize,
+        characters: Vec<char>,
+    },
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalibrationError::TotalOverflow {
+                line_number,
+                total,
+                value,
+            } => write!(
+                f,
+                "calibration total overflows at line {}: {} + {} doesn't fit in a u64",
+                line_number, total, value
+            ),
+            CalibrationError::ValueOverflow {
+                line_number,
+                digits,
+            } => write!(
+                f,
+                "line {}: {}",
+                line_number,
+                ExtractionError::TooManyDigits { digits: *digits }
+            ),
+            CalibrationError::UnsupportedCharacters {
+                line_number,
+                characters,
+            } => write!(
+                f,
+                "line {}: {}",
+                line_number,
+                UnicodeError {
+                    characters: characters.clone()
+                }
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+// Running total of a document. Additions are checked, so a total that can't be
+// represented is reported instead of wrapping around or panicking.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CalibrationTotal {
+    pub total: u64,
+    pub lines: usize,
+}
+
+impl CalibrationTotal {
+    pub fn new() -> CalibrationTotal {
+        CalibrationTotal::default()
+    }
+
+    pub fn add(&mut self, value: u64) -> Result<u64, CalibrationError> {
+        self.lines += 1;
+        self.total = self
+            .total
+            .checked_add(value)
+            .ok_or(CalibrationError::TotalOverflow {
+                line_number: self.lines,
+                total: self.total,
+                value,
+            })?;
+
+        Ok(self.total)
+    }
+}
+
+pub struct Calibrator<R: DigitRecognizer> {
+    pub recognizer: R,
+    pub extraction: Extraction,
+}
+
+impl<R: DigitRecognizer> Calibrator<R> {
+    pub fn new(recognizer: R) -> Calibrator<R> {
+        Calibrator {
+            recognizer,
+            extraction: Extraction::default(),
+        }
+    }
+
+    pub fn with_extraction(mut self, extraction: Extraction) -> Calibrator<R> {
+        self.extraction = extraction;
+        self
+    }
+
+    pub fn calibrate_line(&self, line: &str) -> Result<LineCalibration, ExtractionError> {
+        let digits = self.recognizer.find_digits(line);
+        let value = self.extraction.extract(&digits)?;
+
+        Ok(LineCalibration { digits, value })
+    }
+
+    // Like calibrate_line, keeping where every digit was found.
+    pub fn explain(&self, line: &str) -> Result<Explanation, ExtractionError> {
+        let tokens = self.recognizer.guess_tokens(line);
+        let digits: Vec<u8> = tokens.iter().map(|(token, _)| token.digit).collect();
+        let value = self.extraction.extract(&digits)?;
+
+        Ok(Explanation::new(
+            line,
+            &tokens,
+            self.extraction.picks(&digits),
+            value,
+        ))
+    }
+
+    fn line_value(
+        &self,
+        line_number: usize,
+        line: &str,
+    ) -> Result<LineCalibration, CalibrationError> {
+        self.calibrate_line(line).map_err(|err| match err {
+            ExtractionError::TooManyDigits { digits } => CalibrationError::ValueOverflow {
+                line_number,
+                digits,
+            },
+        })
+    }
+
+    fn check_line(&self, line_number: usize, line: &str) -> Result<(), CalibrationError> {
+        self.recognizer
+            .check(line)
+            .map_err(|err| CalibrationError::UnsupportedCharacters {
+                line_number,
+                characters: err.characters,
+            })
+    }
+
+    // Only looks for the first and last digit instead of collecting all of them.
+    pub fn calibration_value(&self, line: &str) -> Option<CalibrationValue> {
+        self.recognizer
+            .first_and_last(line)
+            .map(|(first, last)| CalibrationValue { first, last })
+    }
+
+    // Sums the calibration values of every line in the document. `inspect` sees each
+    // line with its calibration, e.g. to print how the value was found.
+    pub fn calibrate_document<I, S, F>(
+        &self,
+        lines: I,
+        mut inspect: F,
+    ) -> Result<u64, CalibrationError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        F: FnMut(&str, &LineCalibration),
+    {
+        let mut total = CalibrationTotal::new();
+
+        for (index, line) in lines.into_iter().enumerate() {
+            self.check_line(index + 1, line.as_ref())?;
+            let calibration = self.line_value(index + 1, line.as_ref())?;
+            inspect(line.as_ref(), &calibration);
+            total.add(calibration.value())?;
+        }
+
+        Ok(total.total)
+    }
+
+    // Same total as calibrate_document. With the FirstLast extraction it only looks
+    // for the first and last digit of each line, through calibration_value.
+    pub fn total<I, S>(&self, lines: I) -> Result<u64, CalibrationError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.accumulate(lines, CalibrationTotal::new())
+            .map(|total| total.total)
+    }
+
+    // Carries on from `total`, which may already hold earlier lines of the document,
+    // so line numbers in errors stay those of the whole document.
+    pub(crate) fn accumulate<I, S>(
+        &self,
+        lines: I,
+        mut total: CalibrationTotal,
+    ) -> Result<CalibrationTotal, CalibrationError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for line in lines {
+            let line_number = total.lines + 1;
+            self.check_line(line_number, line.as_ref())?;
+            let value = match self.extraction {
+                Extraction::FirstLast => self
+                    .calibration_value(line.as_ref())
+                    .map_or(0, |value| value.value() as u64),
+                _ => self.line_value(line_number, line.as_ref())?.value(),
+            };
+            total.add(value)?;
+        }
+
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digits::{NumericDigits, SpelledDigits};
+    use crate::extraction::Extraction;
+    use crate::unicode::UnicodePolicy;
+
+    #[test]
+    fn test_calibration_value_from_digits() {
+        assert_eq!(
+            CalibrationValue::from_digits(&[1, 2, 3]),
+            Some(CalibrationValue { first: 1, last: 3 })
+        );
+        assert_eq!(
+            CalibrationValue::from_digits(&[7]),
+            Some(CalibrationValue { first: 7, last: 7 })
+        );
+        assert_eq!(CalibrationValue::from_digits(&[]), None);
+        assert_eq!(CalibrationValue { first: 7, last: 7 }.value(), 77);
+    }
+
+    #[test]
+    fn test_numeric_calibrate_line() {
+        let calibrator = Calibrator::new(NumericDigits::default());
+
+        assert_eq!(
+            calibrator.calibrate_line("abc123def456").unwrap().value(),
+            16
+        );
+        assert_eq!(
+            calibrator.calibrate_line("no digits here").unwrap().value(),
+            0
+        );
+        assert_eq!(calibrator.calibrate_line("111").unwrap().value(), 11);
+        assert_eq!(calibrator.calibrate_line("2").unwrap().value(), 22);
+        assert_eq!(
+            calibrator
+                .calibrate_line("abc1pqsttwo33four")
+                .unwrap()
+                .value(),
+            13
+        );
+        assert_eq!(
+            calibrator.calibrate_line("zoneight234").unwrap().value(),
+            24
+        );
+    }
+
+    #[test]
+    fn test_spelled_calibrate_line() {
+        let calibrator = Calibrator::new(SpelledDigits::default());
+
+        assert_eq!(
+            calibrator.calibrate_line("abc123def456").unwrap().value(),
+            16
+        );
+        assert_eq!(
+            calibrator.calibrate_line("no digits here").unwrap().value(),
+            0
+        );
+        assert_eq!(calibrator.calibrate_line("111").unwrap().value(), 11);
+        assert_eq!(calibrator.calibrate_line("2").unwrap().value(), 22);
+        assert_eq!(
+            calibrator
+                .calibrate_line("abc1pqsttwo33four")
+                .unwrap()
+                .value(),
+            14
+        );
+        assert_eq!(
+            calibrator.calibrate_line("zoneight234").unwrap().value(),
+            14
+        );
+    }
+
+    #[test]
+    fn test_line_calibration_display() {
+        let calibrator = Calibrator::new(SpelledDigits::default());
+
+        assert_eq!(
+            calibrator
+                .calibrate_line("zoneight234")
+                .unwrap()
+                .to_string(),
+            "14 <- \"18234\""
+        );
+    }
+
+    #[test]
+    fn test_explain_line() {
+        let calibrator = Calibrator::new(SpelledDigits::default());
+        let explanation = calibrator.explain("zoneight234").unwrap();
+
+        assert_eq!(explanation.value, Some(14));
+        assert_eq!(explanation.tokens.first().unwrap().text, "one");
+        assert_eq!(explanation.tokens.last().unwrap().text, "4");
+        assert_eq!(explanation.picks, vec![(0, "first"), (4, "last")]);
+        assert_eq!(
+            explanation.tokens.len(),
+            calibrator
+                .calibrate_line("zoneight234")
+                .unwrap()
+                .digits
+                .len()
+        );
+    }
+
+    #[test]
+    fn test_calibrate_document() {
+        let document = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        let calibrator = Calibrator::new(NumericDigits::default());
+        let mut inspected = Vec::new();
+
+        let total = calibrator.calibrate_document(document.lines(), |line, calibration| {
+            inspected.push(format!("{} <- {}", calibration, line));
+        });
+
+        assert_eq!(total, Ok(142));
+        assert_eq!(calibrator.total(document.lines()), Ok(142));
+        assert_eq!(inspected[0], "12 <- \"12\" <- 1abc2");
+        assert_eq!(inspected.len(), 4);
+    }
+
+    #[test]
+    fn test_calibrate_document_spelled_digits() {
+        let document = r#"two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen"#;
+        let calibrator = Calibrator::new(SpelledDigits::default());
+
+        assert_eq!(
+            calibrator.calibrate_document(document.lines(), |_, _| {}),
+            Ok(281)
+        );
+        assert_eq!(calibrator.total(document.lines()), Ok(281));
+    }
+
+    #[test]
+    fn test_calibrate_large_document() {
+        // 99 * 1000 would overflow the u8 the values are computed in
+        let document = vec!["9abc9"; 1000];
+        let calibrator = Calibrator::new(NumericDigits::default());
+
+        assert_eq!(
+            calibrator.calibrate_document(document, |_, _| {}),
+            Ok(99_000)
+        );
+    }
+
+    #[test]
+    fn test_reject_unsupported_characters() {
+        let document = "1abc2\npqr½stu8vwx\n٣";
+        let calibrator = Calibrator::new(NumericDigits::new(UnicodePolicy::Reject));
+        let expected = CalibrationError::UnsupportedCharacters {
+            line_number: 2,
+            characters: vec!['½'],
+        };
+
+        assert_eq!(
+            calibrator.calibrate_document(document.lines(), |_, _| {}),
+            Err(expected.clone())
+        );
+        assert_eq!(calibrator.total(document.lines()), Err(expected.clone()));
+        assert_eq!(
+            expected.to_string(),
+            "line 2: unsupported numeric characters '½' (U+00BD)"
+        );
+
+        let calibrator = Calibrator::new(NumericDigits::new(UnicodePolicy::DecimalDigits));
+        assert_eq!(calibrator.total(document.lines()), Ok(12 + 88 + 33));
+    }
+
+    #[test]
+    fn test_calibrate_with_extractions() {
+        let document = "a1b2c3\nxx\n9treb7uchet8";
+        let calibrate = |extraction| {
+            Calibrator::new(NumericDigits::default())
+                .with_extraction(extraction)
+                .total(document.lines())
+        };
+
+        assert_eq!(calibrate(Extraction::FirstLast), Ok(13 + 98));
+        assert_eq!(calibrate(Extraction::AllDigits), Ok(123 + 978));
+        assert_eq!(calibrate(Extraction::First(2)), Ok(12 + 97));
+        assert_eq!(calibrate(Extraction::Last(2)), Ok(23 + 78));
+        assert_eq!(calibrate(Extraction::LargestPair), Ok(23 + 98));
+        assert_eq!(
+            calibrate(Extraction::custom(|digits| Some(digits.len() as u64))),
+            Ok(3 + 3)
+        );
+    }
+
+    #[test]
+    fn test_extraction_value_overflow() {
+        let document = ["12", "1234567890123456789012"];
+        let calibrator =
+            Calibrator::new(NumericDigits::default()).with_extraction(Extraction::AllDigits);
+        let expected = CalibrationError::ValueOverflow {
+            line_number: 2,
+            digits: 22,
+        };
+
+        assert_eq!(
+            calibrator.calibrate_document(document, |_, _| {}),
+            Err(expected.clone())
+        );
+        assert_eq!(expected.to_string(), "line 2: 22 digits don't fit in a u64");
+    }
+
+    #[test]
+    fn test_calibration_total_overflow() {
+        let mut total = CalibrationTotal {
+            total: u64::MAX - 20,
+            lines: 41,
+        };
+
+        assert_eq!(total.add(12), Ok(u64::MAX - 8));
+        assert_eq!(
+            total.add(99),
+            Err(CalibrationError::TotalOverflow {
+                line_number: 43,
+                total: u64::MAX - 8,
+                value: 99
+            })
+        );
+        assert_eq!(
+            CalibrationError::TotalOverflow {
+                line_number: 43,
+                total: 10,
+                value: 99
+            }
+            .to_string(),
+            "calibration total overflows at line 43: 10 + 99 doesn't fit in a u64"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::process;
+
+use clap::Args;
+use runner::Runner;
+
+use crate::calibration::{CalibrationError, Calibrator};
+use crate::digits::DigitRecognizer;
+use crate::unicode::UnicodePolicy;
+
+// The options both parts calibrate a document with.
+#[derive(Args, Debug)]
+pub struct CalibrationArgs {
+    /// How to treat numeric characters outside of ASCII
+    #[arg(long, value_enum, default_value_t = UnicodePolicy::AsciiOnly)]
+    pub unicode: UnicodePolicy,
+
+    /// Show where the digits of every line were found instead of just its value
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Split the input over this many threads and only print the final value
+    #[arg(long, value_name = "N", conflicts_with = "explain")]
+    pub threads: Option<usize>,
+}
+
+impl CalibrationArgs {
+    // The calibration total of the puzzle input. Every line is printed with its value,
+    // or explained with --explain, unless the input is spread over --threads. Exits on
+    // the first error.
+    pub fn calibrate<R: DigitRecognizer + Sync>(
+        &self,
+        runner: &mut Runner,
+        calibrator: &Calibrator<R>,
+    ) -> u64 {
+        let exit = |err: CalibrationError| -> u64 {
+            eprintln!("{}", err);
+            process::exit(1);
+        };
+        let mut input = runner.open_input(env!("CARGO_MANIFEST_DIR"));
+
+        if let Some(threads) = self.threads {
+            let chunks = input.line_chunks(threads).unwrap_or_else(|err| {
+                eprintln!("Could not read puzzle input: {}", err);
+                process::exit(1);
+            });
+            return calibrator.total_chunks(&chunks).unwrap_or_else(exit);
+        }
+
+        let lines = input
+            .lines()
+            .map(|line| line.expect("Could not read puzzle input"));
+
+        calibrator
+            .calibrate_document(lines, |line, calibration| {
+                match self.explain.then(|| calibrator.explain(line)) {
+                    Some(Ok(explanation)) if runner.is_visualizing() => {
+                        runner.visualize("calibration-line", || explanation.frame())
+                    }
+                    Some(Ok(explanation)) => println!("{}\n", explanation.render()),
+                    _ => println!("{} <- {}", calibration, line),
+                }
+            })
+            .unwrap_or_else(exit)
+    }
+}
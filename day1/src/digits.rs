@@ -0,0 +1,285 @@
+use crate::explain::Guess;
+use crate::scanner::{DigitScanner, DigitToken};
+use crate::unicode::{UnicodeError, UnicodePolicy};
+use crate::vocabulary::Vocabulary;
+
+// A strategy for finding the digits of a calibration line, in the order they appear.
+pub trait DigitRecognizer {
+    // Each digit together with the span of the line it was read from.
+    fn find_tokens(&self, line: &str) -> Vec<DigitToken>;
+
+    fn find_digits(&self, line: &str) -> Vec<u8> {
+        self.find_tokens(line)
+            .iter()
+            .map(|token| token.digit)
+            .collect()
+    }
+
+    // Like find_tokens, with the word each token was taken for when it wasn't spelled
+    // the way the vocabulary spells it. Only recognizers that guess override this.
+    fn guess_tokens(&self, line: &str) -> Vec<(DigitToken, Option<Guess>)> {
+        self.find_tokens(line)
+            .into_iter()
+            .map(|token| (token, None))
+            .collect()
+    }
+
+    // Recognizers that can find the first and last digit without going through
+    // every digit in between should override this.
+    fn first_and_last(&self, line: &str) -> Option<(u8, u8)> {
+        let digits = self.find_digits(line);
+        Some((*digits.first()?, *digits.last()?))
+    }
+
+    // Lets a recognizer refuse a line outright, e.g. for characters it won't guess at.
+    fn check(&self, _line: &str) -> Result<(), UnicodeError> {
+        Ok(())
+    }
+}
+
+// Part 1 rules: only numeric characters are digits. The policy decides which
+// characters beyond '0'..='9' that takes in.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NumericDigits {
+    pub policy: UnicodePolicy,
+}
+
+impl NumericDigits {
+    pub fn new(policy: UnicodePolicy) -> NumericDigits {
+        NumericDigits { policy }
+    }
+}
+
+impl DigitRecognizer for NumericDigits {
+    fn find_tokens(&self, line: &str) -> Vec<DigitToken> {
+        line.char_indices()
+            .filter_map(|(start, c)| {
+                let digit = self.policy.digit(c)?;
+                Some(DigitToken {
+                    start,
+                    end: start + c.len_utf8(),
+                    digit,
+                })
+            })
+            .collect()
+    }
+
+    fn find_digits(&self, line: &str) -> Vec<u8> {
+        line.chars().filter_map(|c| self.policy.digit(c)).collect()
+    }
+
+    fn first_and_last(&self, line: &str) -> Option<(u8, u8)> {
+        let first = line.chars().find_map(|c| self.policy.digit(c))?;
+        let last = line.chars().rev().find_map(|c| self.policy.digit(c))?;
+        Some((first, last))
+    }
+
+    fn check(&self, line: &str) -> Result<(), UnicodeError> {
+        self.policy.check(line)
+    }
+}
+
+// Part 2 rules: digits can also be spelled out, and spelled digits may overlap ("oneight").
+pub struct SpelledDigits {
+    pub vocabulary: Vocabulary,
+    pub policy: UnicodePolicy,
+    scanner: DigitScanner,
+}
+
+impl SpelledDigits {
+    pub fn new(vocabulary: Vocabulary) -> SpelledDigits {
+        SpelledDigits::with_policy(vocabulary, UnicodePolicy::default())
+    }
+
+    pub fn with_policy(vocabulary: Vocabulary, policy: UnicodePolicy) -> SpelledDigits {
+        let scanner = DigitScanner::new(&vocabulary, policy);
+        SpelledDigits {
+            vocabulary,
+            policy,
+            scanner,
+        }
+    }
+}
+
+impl Default for SpelledDigits {
+    fn default() -> SpelledDigits {
+        SpelledDigits::new(Vocabulary::english())
+    }
+}
+
+impl DigitRecognizer for SpelledDigits {
+    fn find_tokens(&self, line: &str) -> Vec<DigitToken> {
+        self.scanner.tokens(line)
+    }
+
+    fn first_and_last(&self, line: &str) -> Option<(u8, u8)> {
+        let first = self.scanner.first(line)?;
+        let last = self.scanner.last(line)?;
+        Some((first.digit, last.digit))
+    }
+
+    fn check(&self, line: &str) -> Result<(), UnicodeError> {
+        self.policy.check(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_find_digits() {
+        assert_eq!(
+            NumericDigits::default().find_digits("abc123def456"),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(
+            NumericDigits::default().find_digits("no digits here"),
+            vec![]
+        );
+        assert_eq!(NumericDigits::default().find_digits("111"), vec![1, 1, 1]);
+        assert_eq!(NumericDigits::default().find_digits("2"), vec![2]);
+        assert_eq!(
+            NumericDigits::default().find_digits("fourfive6seven"),
+            vec![6]
+        );
+        assert_eq!(
+            NumericDigits::default().find_digits("zoneight234"),
+            vec![2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_spelled_find_digits() {
+        assert_eq!(
+            SpelledDigits::default().find_digits("abc123def456"),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(
+            SpelledDigits::default().find_digits("no digits here"),
+            vec![]
+        );
+        assert_eq!(SpelledDigits::default().find_digits("111"), vec![1, 1, 1]);
+        assert_eq!(SpelledDigits::default().find_digits("2"), vec![2]);
+        assert_eq!(
+            SpelledDigits::default().find_digits("fourfive6seven"),
+            vec![4, 5, 6, 7]
+        );
+        assert_eq!(
+            SpelledDigits::default().find_digits("zoneight234"),
+            vec![1, 8, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_numeric_unicode_policies() {
+        let line = "a½b٣c7d３";
+        let ascii = NumericDigits::new(UnicodePolicy::AsciiOnly);
+        let decimal = NumericDigits::new(UnicodePolicy::DecimalDigits);
+        let reject = NumericDigits::new(UnicodePolicy::Reject);
+
+        assert_eq!(ascii.find_digits(line), vec![7]);
+        assert_eq!(decimal.find_digits(line), vec![3, 7, 3]);
+        assert_eq!(decimal.first_and_last(line), Some((3, 3)));
+        assert!(ascii.check(line).is_ok());
+        assert_eq!(
+            reject.check(line).unwrap_err().characters,
+            vec!['½', '٣', '３']
+        );
+    }
+
+    #[test]
+    fn test_spelled_unicode_policies() {
+        let line = "٣one½";
+        let ascii = SpelledDigits::with_policy(Vocabulary::english(), UnicodePolicy::AsciiOnly);
+        let decimal =
+            SpelledDigits::with_policy(Vocabulary::english(), UnicodePolicy::DecimalDigits);
+        let reject = SpelledDigits::with_policy(Vocabulary::english(), UnicodePolicy::Reject);
+
+        assert_eq!(ascii.find_digits(line), vec![1]);
+        assert_eq!(decimal.find_digits(line), vec![3, 1]);
+        assert_eq!(decimal.first_and_last(line), Some((3, 1)));
+        assert!(reject.check(line).is_err());
+    }
+
+    #[test]
+    fn test_find_tokens() {
+        let tokens = NumericDigits::new(UnicodePolicy::DecimalDigits).find_tokens("é٣x7");
+
+        assert_eq!(
+            tokens,
+            vec![
+                DigitToken {
+                    start: 2,
+                    end: 4,
+                    digit: 3
+                },
+                DigitToken {
+                    start: 5,
+                    end: 6,
+                    digit: 7
+                },
+            ]
+        );
+        assert_eq!(
+            SpelledDigits::default().find_tokens("xtwone3")[1],
+            DigitToken {
+                start: 3,
+                end: 6,
+                digit: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_first_and_last() {
+        let spelled = SpelledDigits::default();
+
+        assert_eq!(
+            NumericDigits::default().first_and_last("a1b2c3d4e5f"),
+            Some((1, 5))
+        );
+        assert_eq!(
+            NumericDigits::default().first_and_last("treb7uchet"),
+            Some((7, 7))
+        );
+        assert_eq!(
+            NumericDigits::default().first_and_last("no digits here"),
+            None
+        );
+        assert_eq!(spelled.first_and_last("zoneight234"), Some((1, 4)));
+        assert_eq!(spelled.first_and_last("xtwone3four"), Some((2, 4)));
+        assert_eq!(spelled.first_and_last("oneight"), Some((1, 8)));
+        assert_eq!(spelled.first_and_last("no digits here"), None);
+    }
+
+    #[test]
+    fn test_spelled_find_digits_other_languages() {
+        let spanish = SpelledDigits::new(Vocabulary::spanish());
+        let german = SpelledDigits::new(Vocabulary::german());
+        let french = SpelledDigits::new(Vocabulary::french());
+
+        assert_eq!(spanish.find_digits("dosiete4UNOcho"), vec![2, 7, 4, 1, 8]);
+        assert_eq!(german.find_digits("xFünfzig3achtzehn"), vec![5, 3, 8]);
+        assert_eq!(french.find_digits("troiseptZÉRO"), vec![3, 7, 0]);
+        assert_eq!(french.find_digits("unedeux"), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_spelled_find_digits_ignores_case() {
+        assert_eq!(
+            SpelledDigits::default().find_digits("OneIGHT2thrEE"),
+            vec![1, 8, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_longer_words_win() {
+        let vocabulary = Vocabulary::new("custom", &[("ein", 1), ("eins", 1), ("einsam", 7)]);
+
+        assert_eq!(
+            SpelledDigits::new(vocabulary).find_digits("einsam"),
+            vec![7]
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use visualize::{Color, Frame, Style};
+
+use crate::scanner::DigitToken;
+
+// The vocabulary word a misspelled token was read as, and how sure that reading is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess {
+    pub word: String,
+    pub confidence: f64,
+}
+
+// A digit token with the text it was read from. `start` and `end` are byte offsets
+// into the line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenSpan {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+    pub digit: u8,
+    pub guess: Option<Guess>,
+}
+
+// Every token found in a line, which of them the extraction policy picked, and the
+// value the line ended up with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub line: String,
+    pub tokens: Vec<TokenSpan>,
+    // indices into `tokens`, with the name the policy gives each pick, e.g. "first"
+    pub picks: Vec<(usize, &'static str)>,
+    pub value: Option<u64>,
+}
+
+const TOKEN_STYLE: Style = Style {
+    color: Color::Yellow,
+    bold: false,
+};
+const CHOSEN_STYLE: Style = Style {
+    color: Color::Green,
+    bold: true,
+};
+
+impl Explanation {
+    pub fn new(
+        line: &str,
+        tokens: &[(DigitToken, Option<Guess>)],
+        picks: Vec<(usize, &'static str)>,
+        value: Option<u64>,
+    ) -> Explanation {
+        let tokens = tokens
+            .iter()
+            .map(|(token, guess)| TokenSpan {
+                start: token.start,
+                end: token.end,
+                text: line[token.start..token.end].to_string(),
+                digit: token.digit,
+                guess: guess.clone(),
+            })
+            .collect();
+
+        Explanation {
+            line: line.to_string(),
+            tokens,
+            picks,
+            value,
+        }
+    }
+
+    fn is_chosen(&self, index: usize) -> bool {
+        self.picks.iter().any(|&(picked, _)| picked == index)
+    }
+
+    // Character columns of a token, for drawing it under the line.
+    fn columns(&self, token: &TokenSpan) -> std::ops::Range<usize> {
+        let start = self.line[..token.start].chars().count();
+        start..start + token.text.chars().count()
+    }
+
+    // Tokens are underlined with `~`, the picked ones with `^`,
+    // and each token's digit is written under its start.
+    fn rows(&self) -> (String, String) {
+        let width = self.line.chars().count();
+        let mut underline = vec![' '; width];
+        let mut digits = vec![' '; width];
+
+        // chosen tokens go last so they stay visible where tokens overlap
+        let mut order: Vec<usize> = (0..self.tokens.len()).collect();
+        order.sort_by_key(|&index| self.is_chosen(index));
+        for index in order {
+            let columns = self.columns(&self.tokens[index]);
+            let mark = if self.is_chosen(index) { '^' } else { '~' };
+            underline[columns.clone()].fill(mark);
+            digits[columns.start] = char::from(b'0' + self.tokens[index].digit);
+        }
+
+        let trim = |row: Vec<char>| row.into_iter().collect::<String>().trim_end().to_string();
+        (trim(underline), trim(digits))
+    }
+
+    fn summary(&self) -> String {
+        let value = self.value.unwrap_or(0);
+        if self.picks.is_empty() {
+            return format!("no digits -> {}", value);
+        }
+
+        let picks: Vec<String> = self
+            .picks
+            .iter()
+            .map(|&(index, name)| {
+                let token = &self.tokens[index];
+                format!(
+                    "{} {} ({:?} at {}..{})",
+                    name, token.digit, token.text, token.start, token.end
+                )
+            })
+            .collect();
+        format!("{} -> {}", picks.join(", "), value)
+    }
+
+    // The misspelled tokens with the words they were read as, e.g. "six (fix, 0.67)".
+    fn guesses(&self) -> Option<String> {
+        let guesses: Vec<String> = self
+            .tokens
+            .iter()
+            .filter_map(|token| {
+                let guess = token.guess.as_ref()?;
+                Some(format!(
+                    "{} ({}, {:.2})",
+                    guess.word, token.text, guess.confidence
+                ))
+            })
+            .collect();
+        (!guesses.is_empty()).then(|| format!("misspelled: {}", guesses.join(", ")))
+    }
+
+    pub fn render(&self) -> String {
+        let (underline, digits) = self.rows();
+        let mut rendered = format!(
+            "{}\n{}\n{}\n{}",
+            self.line,
+            underline,
+            digits,
+            self.summary()
+        );
+        if let Some(guesses) = self.guesses() {
+            rendered.push('\n');
+            rendered.push_str(&guesses);
+        }
+        rendered
+    }
+
+    // Same layout as `render`, with the tokens highlighted in the line itself.
+    pub fn frame(&self) -> Frame {
+        let (underline, digits) = self.rows();
+        let mut frame = Frame::new("Day 1: calibration line");
+        frame.push_line(&self.line, Style::plain());
+        frame.push_line(&underline, Style::plain());
+        frame.push_line(&digits, Style::plain());
+        frame.push_line(&self.summary(), Style::plain());
+        if let Some(guesses) = self.guesses() {
+            frame.push_line(&guesses, Style::plain());
+        }
+
+        let mut order: Vec<usize> = (0..self.tokens.len()).collect();
+        order.sort_by_key(|&index| self.is_chosen(index));
+        for index in order {
+            let style = if self.is_chosen(index) {
+                CHOSEN_STYLE
+            } else {
+                TOKEN_STYLE
+            };
+            for row in 0..3 {
+                frame.style_range(row, self.columns(&self.tokens[index]), style);
+            }
+        }
+
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calibration::Calibrator;
+    use crate::digits::SpelledDigits;
+    use crate::extraction::Extraction;
+
+    fn explain(line: &str) -> Explanation {
+        Calibrator::new(SpelledDigits::default())
+            .explain(line)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_token_spans() {
+        let explanation = explain("xtwone3four");
+
+        assert_eq!(explanation.picks, vec![(0, "first"), (3, "last")]);
+        assert_eq!(
+            explanation.tokens.first(),
+            Some(&TokenSpan {
+                start: 1,
+                end: 4,
+                text: "two".to_string(),
+                digit: 2,
+                guess: None
+            })
+        );
+        assert_eq!(explanation.tokens[1].text, "one");
+        assert_eq!(explanation.tokens[2].text, "3");
+        assert_eq!(explanation.tokens[3].start, 7);
+        assert_eq!(explanation.value, Some(24));
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            explain("xtwone3four").render(),
+            "xtwone3four\n ^^^~~~^^^^\n 2 1  34\nfirst 2 (\"two\" at 1..4), last 4 (\"four\" at 7..11) -> 24"
+        );
+        assert_eq!(explain("nothing").render(), "nothing\n\n\nno digits -> 0");
+    }
+
+    #[test]
+    fn test_render_follows_extraction() {
+        let calibrator =
+            Calibrator::new(SpelledDigits::default()).with_extraction(Extraction::LargestPair);
+        let rendered = calibrator.explain("1nine3eight").unwrap().render();
+
+        assert_eq!(
+            rendered,
+            "1nine3eight\n~^^^^~^^^^^\n19   38\ntens 9 (\"nine\" at 1..5), ones 8 (\"eight\" at 6..11) -> 98"
+        );
+    }
+
+    #[test]
+    fn test_render_counts_characters() {
+        let rendered = explain("é٣seven").render();
+
+        assert_eq!(rendered.lines().nth(1), Some("  ^^^^^"));
+    }
+
+    #[test]
+    fn test_frame_highlights_tokens() {
+        let frame = explain("xtwone3four").frame();
+
+        assert_eq!(frame.rows[0][0].style, Style::plain());
+        assert_eq!(frame.rows[0][1].style, CHOSEN_STYLE);
+        assert_eq!(frame.rows[0][4].style, TOKEN_STYLE);
+        assert_eq!(frame.rows[0][3].style, CHOSEN_STYLE);
+        assert_eq!(frame.rows[1][8].style, CHOSEN_STYLE);
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+
+use crate::calibration::CalibrationValue;
+
+pub type ExtractFn = Box<dyn Fn(&[u8]) -> Option<u64> + Send + Sync>;
+
+// How the digits found in a line are turned into its calibration value.
+#[derive(Default)]
+pub enum Extraction {
+    // the puzzle's rule: first digit then last digit, a single digit is doubled
+    #[default]
+    FirstLast,
+    // every digit of the line, in order, read as one number
+    AllDigits,
+    // the first k digits read as one number, or all of them when there are fewer
+    First(usize),
+    // the last k digits read as one number, or all of them when there are fewer
+    Last(usize),
+    // the largest number made of two digits in the order they appear; a single
+    // digit is doubled like with FirstLast
+    LargestPair,
+    Custom(ExtractFn),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractionError {
+    TooManyDigits { digits: usize },
+}
+
+impl fmt::Display for ExtractionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExtractionError::TooManyDigits { digits } => {
+                write!(f, "{} digits don't fit in a u64", digits)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExtractionError {}
+
+fn concatenate(digits: &[u8]) -> Result<Option<u64>, ExtractionError> {
+    if digits.is_empty() {
+        return Ok(None);
+    }
+
+    digits
+        .iter()
+        .try_fold(0u64, |value, &digit| {
+            value.checked_mul(10)?.checked_add(digit as u64)
+        })
+        .map(Some)
+        .ok_or(ExtractionError::TooManyDigits {
+            digits: digits.len(),
+        })
+}
+
+// Positions of the two digits making the largest pair, the earliest pair on ties.
+fn largest_pair_positions(digits: &[u8]) -> Option<(usize, usize)> {
+    if digits.len() == 1 {
+        return Some((0, 0));
+    }
+
+    // the best pair ending at each digit uses the largest digit before it
+    let pair = |(first, second): (usize, usize)| digits[first] * 10 + digits[second];
+    let mut best_first = 0;
+    let mut best: Option<(usize, usize)> = None;
+    for second in 1..digits.len() {
+        if best.is_none_or(|best| pair((best_first, second)) > pair(best)) {
+            best = Some((best_first, second));
+        }
+        if digits[second] > digits[best_first] {
+            best_first = second;
+        }
+    }
+
+    best
+}
+
+fn largest_pair(digits: &[u8]) -> Option<u64> {
+    largest_pair_positions(digits)
+        .map(|(first, second)| digits[first] as u64 * 10 + digits[second] as u64)
+}
+
+impl Extraction {
+    pub fn custom<F>(extract: F) -> Extraction
+    where
+        F: Fn(&[u8]) -> Option<u64> + Send + Sync + 'static,
+    {
+        Extraction::Custom(Box::new(extract))
+    }
+
+    // None when the line has no value, e.g. because it has no digits.
+    pub fn extract(&self, digits: &[u8]) -> Result<Option<u64>, ExtractionError> {
+        match self {
+            Extraction::FirstLast => {
+                Ok(CalibrationValue::from_digits(digits).map(|value| value.value() as u64))
+            }
+            Extraction::AllDigits => concatenate(digits),
+            Extraction::First(k) => concatenate(&digits[..digits.len().min(*k)]),
+            Extraction::Last(k) => concatenate(&digits[digits.len().saturating_sub(*k)..]),
+            Extraction::LargestPair => Ok(largest_pair(digits)),
+            Extraction::Custom(extract) => Ok(extract(digits)),
+        }
+    }
+
+    // Which of the digits the value is made of, each with the name this policy gives
+    // it. A custom extraction may read any of them, so it gets all of them.
+    pub fn picks(&self, digits: &[u8]) -> Vec<(usize, &'static str)> {
+        let count = digits.len();
+        let every = |range: std::ops::Range<usize>| range.map(|index| (index, "digit")).collect();
+        match self {
+            Extraction::FirstLast if count > 0 => vec![(0, "first"), (count - 1, "last")],
+            Extraction::FirstLast => vec![],
+            Extraction::AllDigits | Extraction::Custom(_) => every(0..count),
+            Extraction::First(k) => every(0..count.min(*k)),
+            Extraction::Last(k) => every(count.saturating_sub(*k)..count),
+            Extraction::LargestPair => match largest_pair_positions(digits) {
+                Some((first, second)) => vec![(first, "tens"), (second, "ones")],
+                None => vec![],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_last() {
+        assert_eq!(Extraction::FirstLast.extract(&[1, 2, 3]), Ok(Some(13)));
+        assert_eq!(Extraction::FirstLast.extract(&[7]), Ok(Some(77)));
+        assert_eq!(Extraction::FirstLast.extract(&[]), Ok(None));
+    }
+
+    #[test]
+    fn test_concatenating_extractions() {
+        let digits = [4, 0, 2, 9, 1];
+
+        assert_eq!(Extraction::AllDigits.extract(&digits), Ok(Some(40291)));
+        assert_eq!(Extraction::First(3).extract(&digits), Ok(Some(402)));
+        assert_eq!(Extraction::Last(2).extract(&digits), Ok(Some(91)));
+        assert_eq!(Extraction::Last(9).extract(&digits), Ok(Some(40291)));
+        assert_eq!(Extraction::First(0).extract(&digits), Ok(None));
+        assert_eq!(Extraction::AllDigits.extract(&[]), Ok(None));
+        assert_eq!(
+            Extraction::AllDigits.extract(&[9; 20]),
+            Err(ExtractionError::TooManyDigits { digits: 20 })
+        );
+        assert_eq!(
+            Extraction::AllDigits.extract(&[9; 19]),
+            Ok(Some(9_999_999_999_999_999_999))
+        );
+    }
+
+    #[test]
+    fn test_largest_pair() {
+        assert_eq!(Extraction::LargestPair.extract(&[1, 9, 3, 8]), Ok(Some(98)));
+        assert_eq!(Extraction::LargestPair.extract(&[9, 1]), Ok(Some(91)));
+        assert_eq!(Extraction::LargestPair.extract(&[1, 9]), Ok(Some(19)));
+        assert_eq!(Extraction::LargestPair.extract(&[5]), Ok(Some(55)));
+        assert_eq!(Extraction::LargestPair.extract(&[]), Ok(None));
+    }
+
+    #[test]
+    fn test_picks() {
+        let digits = [1, 9, 3, 8];
+
+        assert_eq!(
+            Extraction::FirstLast.picks(&digits),
+            vec![(0, "first"), (3, "last")]
+        );
+        assert_eq!(
+            Extraction::FirstLast.picks(&[7]),
+            vec![(0, "first"), (0, "last")]
+        );
+        assert_eq!(
+            Extraction::LargestPair.picks(&digits),
+            vec![(1, "tens"), (3, "ones")]
+        );
+        assert_eq!(
+            Extraction::First(2).picks(&digits),
+            vec![(0, "digit"), (1, "digit")]
+        );
+        assert_eq!(Extraction::Last(1).picks(&digits), vec![(3, "digit")]);
+        assert_eq!(Extraction::AllDigits.picks(&digits).len(), 4);
+        assert_eq!(Extraction::FirstLast.picks(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_custom_extraction() {
+        let sum = Extraction::custom(|digits| Some(digits.iter().map(|&d| d as u64).sum()));
+
+        assert_eq!(sum.extract(&[1, 2, 3]), Ok(Some(6)));
+    }
+}
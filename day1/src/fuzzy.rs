@@ -0,0 +1,387 @@
+use crate::digits::{DigitRecognizer, SpelledDigits};
+use crate::explain::Guess;
+use crate::scanner::DigitToken;
+use crate::unicode::UnicodeError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FuzzyConfig {
+    // the most edits (insertions, deletions, substitutions or swaps of two
+    // neighbouring letters) a word may be off by
+    pub max_distance: usize,
+    // matches below this confidence are dropped. The default lets a four-letter
+    // word like "four" be one edit off, which is worth 0.75
+    pub min_confidence: f64,
+    // text shorter than this never matches. Plenty of three-letter words are one
+    // edit from a digit word ("ten", "fix", "sit"), so the default is four
+    pub min_length: usize,
+    // whether to look for misspelled words in lines of several words separated by
+    // whitespace. Ordinary prose is full of words one edit from a digit word
+    // ("there", "even", "line"), so by default such lines only get exact tokens
+    pub read_prose: bool,
+}
+
+impl Default for FuzzyConfig {
+    fn default() -> FuzzyConfig {
+        FuzzyConfig {
+            max_distance: 1,
+            min_confidence: 0.75,
+            min_length: 4,
+            read_prose: false,
+        }
+    }
+}
+
+impl FuzzyConfig {
+    pub fn new(max_distance: usize) -> FuzzyConfig {
+        FuzzyConfig {
+            max_distance,
+            ..FuzzyConfig::default()
+        }
+    }
+}
+
+// A digit with how far its text was from the vocabulary word. Confidence is 1.0
+// for exact matches and drops with every edit relative to the word's length.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyToken {
+    pub token: DigitToken,
+    pub word: String,
+    pub distance: usize,
+    pub confidence: f64,
+}
+
+// Spelled digits with typos: "sevn" reads as 7 and "fuor" as 4. Exact tokens are
+// found first, exactly like SpelledDigits does; misspelled words are then only
+// looked for in the text no exact token covers.
+pub struct FuzzyDigits {
+    pub exact: SpelledDigits,
+    pub config: FuzzyConfig,
+}
+
+// Optimal string alignment distance: Levenshtein plus swaps of adjacent letters.
+pub fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+impl FuzzyDigits {
+    pub fn new(exact: SpelledDigits, config: FuzzyConfig) -> FuzzyDigits {
+        FuzzyDigits { exact, config }
+    }
+
+    // Every candidate: a run of letters within the allowed distance of a word.
+    fn candidates(&self, line: &str) -> Vec<FuzzyToken> {
+        if !self.config.read_prose && line.trim().contains(char::is_whitespace) {
+            return Vec::new();
+        }
+
+        let chars: Vec<(usize, char)> = line.char_indices().collect();
+        let lowered: Vec<char> = chars.iter().map(|&(_, c)| lowercase(c)).collect();
+        let words: Vec<(Vec<char>, &str, u8)> = self
+            .exact
+            .vocabulary
+            .words()
+            .map(|(word, digit)| (word.chars().collect(), word, digit))
+            .collect();
+        let k = self.config.max_distance;
+        let mut candidates = Vec::new();
+
+        for start in 0..chars.len() {
+            for (word_chars, word, digit) in &words {
+                let shortest = word_chars
+                    .len()
+                    .saturating_sub(k)
+                    .max(self.config.min_length)
+                    .max(1);
+                let longest = (word_chars.len() + k).min(chars.len() - start);
+
+                for len in shortest..=longest {
+                    let text = &lowered[start..start + len];
+                    if !text.iter().all(|c| c.is_alphabetic()) {
+                        break;
+                    }
+
+                    let distance = edit_distance(text, word_chars);
+                    let confidence = 1.0 - distance as f64 / word_chars.len() as f64;
+                    if distance == 0 || distance > k || confidence < self.config.min_confidence {
+                        continue;
+                    }
+
+                    let end = chars.get(start + len).map_or(line.len(), |&(i, _)| i);
+                    candidates.push(FuzzyToken {
+                        token: DigitToken {
+                            start: chars[start].0,
+                            end,
+                            digit: *digit,
+                        },
+                        word: word.to_string(),
+                        distance,
+                        confidence,
+                    });
+                }
+            }
+        }
+
+        candidates
+    }
+
+    // Exact tokens, plus the fuzzy candidates that don't overlap any of them or
+    // each other. Candidates are taken closest match first, then leftmost, then
+    // longest, then in vocabulary order, so ties always resolve the same way.
+    pub fn fuzzy_tokens(&self, line: &str) -> Vec<FuzzyToken> {
+        let mut tokens: Vec<FuzzyToken> = self
+            .exact
+            .find_tokens(line)
+            .into_iter()
+            .map(|token| FuzzyToken {
+                token,
+                word: line[token.start..token.end].to_lowercase(),
+                distance: 0,
+                confidence: 1.0,
+            })
+            .collect();
+
+        // sort_by_key is stable, so vocabulary order breaks the remaining ties
+        let mut candidates = self.candidates(line);
+        candidates.sort_by_key(|candidate| {
+            (
+                candidate.distance,
+                candidate.token.start,
+                std::cmp::Reverse(candidate.token.end),
+            )
+        });
+
+        for candidate in candidates {
+            let overlaps = tokens.iter().any(|taken| {
+                candidate.token.start < taken.token.end && taken.token.start < candidate.token.end
+            });
+            if !overlaps {
+                tokens.push(candidate);
+            }
+        }
+
+        tokens.sort_by_key(|token| token.token.start);
+        tokens
+    }
+}
+
+impl DigitRecognizer for FuzzyDigits {
+    fn find_tokens(&self, line: &str) -> Vec<DigitToken> {
+        self.fuzzy_tokens(line)
+            .into_iter()
+            .map(|fuzzy| fuzzy.token)
+            .collect()
+    }
+
+    fn guess_tokens(&self, line: &str) -> Vec<(DigitToken, Option<Guess>)> {
+        self.fuzzy_tokens(line)
+            .into_iter()
+            .map(|fuzzy| {
+                let guess = (fuzzy.distance > 0).then_some(Guess {
+                    word: fuzzy.word,
+                    confidence: fuzzy.confidence,
+                });
+                (fuzzy.token, guess)
+            })
+            .collect()
+    }
+
+    fn check(&self, line: &str) -> Result<(), UnicodeError> {
+        self.exact.check(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calibration::Calibrator;
+    use crate::vocabulary::Vocabulary;
+
+    fn distance(a: &str, b: &str) -> usize {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        edit_distance(&a, &b)
+    }
+
+    fn digits_with_confidence(fuzzy: &FuzzyDigits, line: &str) -> Vec<(u8, f64)> {
+        fuzzy
+            .fuzzy_tokens(line)
+            .iter()
+            .map(|fuzzy| (fuzzy.token.digit, fuzzy.confidence))
+            .collect()
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(distance("seven", "seven"), 0);
+        assert_eq!(distance("sevn", "seven"), 1);
+        assert_eq!(distance("fuor", "four"), 1);
+        assert_eq!(distance("fxve", "five"), 1);
+        assert_eq!(distance("eihgt", "eight"), 1);
+        assert_eq!(distance("", "two"), 3);
+        assert_eq!(distance("nien", "nine"), 1);
+    }
+
+    #[test]
+    fn test_fuzzy_digits() {
+        let fuzzy = FuzzyDigits::new(SpelledDigits::default(), FuzzyConfig::default());
+
+        assert_eq!(
+            digits_with_confidence(&fuzzy, "xsevn2fuor"),
+            vec![(7, 0.8), (2, 1.0), (4, 0.75)]
+        );
+        assert_eq!(fuzzy.find_digits("zoneight234"), vec![1, 8, 2, 3, 4]);
+        assert_eq!(fuzzy.find_digits("SEVN"), vec![7]);
+        assert_eq!(fuzzy.find_digits("no digits here"), vec![]);
+    }
+
+    #[test]
+    fn test_explain_shows_confidence() {
+        let fuzzy = FuzzyDigits::new(SpelledDigits::default(), FuzzyConfig::default());
+        let rendered = Calibrator::new(fuzzy)
+            .explain("xsevn2fuor")
+            .unwrap()
+            .render();
+
+        assert_eq!(
+            rendered.lines().last(),
+            Some("misspelled: seven (sevn, 0.80), four (fuor, 0.75)")
+        );
+    }
+
+    #[test]
+    fn test_short_words_need_confidence() {
+        let fuzzy = FuzzyDigits::new(SpelledDigits::default(), FuzzyConfig::default());
+        let lenient = FuzzyDigits::new(
+            SpelledDigits::default(),
+            FuzzyConfig {
+                max_distance: 1,
+                min_confidence: 0.0,
+                min_length: 1,
+                read_prose: false,
+            },
+        );
+
+        assert_eq!(fuzzy.find_digits("on"), vec![]);
+        assert_eq!(lenient.find_digits("on"), vec![1]);
+    }
+
+    #[test]
+    fn test_three_letter_words() {
+        let fuzzy = FuzzyDigits::new(SpelledDigits::default(), FuzzyConfig::default());
+        let lenient = FuzzyDigits::new(
+            SpelledDigits::default(),
+            FuzzyConfig {
+                min_confidence: 0.6,
+                min_length: 3,
+                ..FuzzyConfig::default()
+            },
+        );
+
+        // ordinary words, not typos
+        for word in ["ten", "fix", "mix", "sit", "too"] {
+            assert_eq!(fuzzy.find_digits(word), vec![], "{}", word);
+        }
+
+        assert_eq!(lenient.find_digits("onr"), vec![1]);
+        assert_eq!(lenient.find_digits("tow"), vec![2]);
+        assert_eq!(lenient.find_digits("sxi"), vec![6]);
+        assert_eq!(lenient.find_digits("sixx"), vec![6]);
+        assert_eq!(
+            digits_with_confidence(&lenient, "twp"),
+            vec![(2, 1.0 - 1.0 / 3.0)]
+        );
+    }
+
+    #[test]
+    fn test_prose_has_no_fuzzy_digits() {
+        let prose =
+            "The elves found the calibration document, but there was a problem: a young elf \
+            had written his own notes over it. Something about getting to the sleigh in time, \
+            fixing the lines and giving every value a second look. However, nobody knew which \
+            of the figures were the originals, so they decided to read it again from the first \
+            line to the last and mix in their own guesses wherever the ink had faded. It took \
+            the whole evening, and even then some of it was never quite fine.";
+        let fuzzy = FuzzyDigits::new(SpelledDigits::default(), FuzzyConfig::default());
+        let exact = SpelledDigits::default();
+
+        assert_eq!(fuzzy.find_tokens(prose), exact.find_tokens(prose));
+        assert_eq!(
+            fuzzy.find_digits("there is a sevn in this line"),
+            exact.find_digits("there is a sevn in this line")
+        );
+
+        let reading = FuzzyDigits::new(
+            SpelledDigits::default(),
+            FuzzyConfig {
+                read_prose: true,
+                ..FuzzyConfig::default()
+            },
+        );
+        assert_eq!(reading.find_digits("a sevn and a fuor"), vec![7, 4]);
+    }
+
+    #[test]
+    fn test_ambiguity_is_deterministic() {
+        // "fivv" is one edit from "five", "nive" one edit from both "five" and "nine"
+        let fuzzy = FuzzyDigits::new(SpelledDigits::default(), FuzzyConfig::default());
+
+        assert_eq!(fuzzy.find_digits("fivv"), vec![5]);
+        assert_eq!(fuzzy.find_digits("nive"), vec![5]);
+        assert_eq!(fuzzy.fuzzy_tokens("nive")[0].word, "five");
+
+        // the closer match wins even when it starts further right
+        let fuzzy = FuzzyDigits::new(
+            SpelledDigits::default(),
+            FuzzyConfig {
+                max_distance: 2,
+                min_confidence: 0.6,
+                ..FuzzyConfig::default()
+            },
+        );
+        let tokens = fuzzy.fuzzy_tokens("xsevn");
+        assert_eq!(tokens.len(), 1);
+        assert_eq!((tokens[0].token.start, tokens[0].token.end), (1, 5));
+        assert_eq!(tokens[0].distance, 1);
+    }
+
+    #[test]
+    fn test_larger_distances() {
+        let fuzzy = FuzzyDigits::new(
+            SpelledDigits::new(Vocabulary::german()),
+            FuzzyConfig {
+                max_distance: 2,
+                min_confidence: 0.6,
+                ..FuzzyConfig::default()
+            },
+        );
+
+        assert_eq!(fuzzy.find_digits("siebn"), vec![7]);
+        assert_eq!(fuzzy.find_digits("sibn"), vec![7]);
+        assert_eq!(fuzzy.find_digits("fünnf"), vec![5]);
+    }
+}
@@ -0,0 +1,11 @@
+pub mod calibration;
+pub mod cli;
+pub mod digits;
+pub mod explain;
+pub mod extraction;
+pub mod fuzzy;
+pub mod parallel;
+pub mod phrases;
+pub mod scanner;
+pub mod unicode;
+pub mod vocabulary;
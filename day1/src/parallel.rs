@@ -0,0 +1,106 @@
+use std::thread;
+
+use runner::input::split_lines;
+
+use crate::calibration::{CalibrationError, CalibrationTotal, Calibrator};
+use crate::digits::DigitRecognizer;
+
+impl<R: DigitRecognizer + Sync> Calibrator<R> {
+    // Same result as `total(document.lines())`, with the lines spread over `threads`
+    // threads. Chunks are summed on their own and the totals combined in document
+    // order; when a chunk fails or the combined total overflows, that chunk is run
+    // again after the ones before it, so the error is exactly the sequential one.
+    pub fn total_parallel(&self, document: &str, threads: usize) -> Result<u64, CalibrationError> {
+        self.total_chunks(&split_lines(document, threads))
+    }
+
+    // Like total_parallel, for a document that is already cut into chunks of whole
+    // lines, for example by `Input::line_chunks`. Every chunk gets its own thread.
+    pub fn total_chunks(&self, chunks: &[&str]) -> Result<u64, CalibrationError> {
+        let results: Vec<Result<CalibrationTotal, CalibrationError>> = thread::scope(|scope| {
+            let handles: Vec<_> = chunks
+                .iter()
+                .map(|chunk| {
+                    scope.spawn(move || self.accumulate(chunk.lines(), CalibrationTotal::new()))
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("Calibration thread panicked"))
+                .collect()
+        });
+
+        let mut total = CalibrationTotal::new();
+        for (chunk, result) in chunks.iter().zip(results) {
+            let combined = result.ok().and_then(|part| {
+                Some(CalibrationTotal {
+                    total: total.total.checked_add(part.total)?,
+                    lines: total.lines + part.lines,
+                })
+            });
+
+            total = match combined {
+                Some(combined) => combined,
+                None => self.accumulate(chunk.lines(), total)?,
+            };
+        }
+
+        Ok(total.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digits::{NumericDigits, SpelledDigits};
+    use crate::extraction::Extraction;
+    use crate::unicode::UnicodePolicy;
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let document = "two1nine\neightwothree\nabcone2threexyz\n\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n".repeat(50);
+        let calibrator = Calibrator::new(SpelledDigits::default());
+        let sequential = calibrator.total(document.lines());
+
+        assert_eq!(sequential, Ok(281 * 50));
+        for threads in [1, 2, 3, 8, 1000] {
+            assert_eq!(calibrator.total_parallel(&document, threads), sequential);
+        }
+    }
+
+    #[test]
+    fn test_parallel_errors_match_sequential() {
+        let document = "1\n2\n3\n½\n5\n٣\n7\n8\n";
+        let calibrator = Calibrator::new(NumericDigits::new(UnicodePolicy::Reject));
+
+        for threads in [1, 2, 3, 8] {
+            assert_eq!(
+                calibrator.total_parallel(document, threads),
+                Err(CalibrationError::UnsupportedCharacters {
+                    line_number: 4,
+                    characters: vec!['½']
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_parallel_overflow_matches_sequential() {
+        // with one line per chunk, the overflow only shows up once the chunk
+        // totals are combined
+        let huge = "9".repeat(25);
+        let calibrator =
+            Calibrator::new(NumericDigits::default()).with_extraction(Extraction::First(19));
+        let document = format!("{}\n{}\n{}\n", huge, huge, huge);
+        let sequential = calibrator.total(document.lines());
+
+        assert!(matches!(
+            sequential,
+            Err(CalibrationError::TotalOverflow { line_number: 2, .. })
+        ));
+        for threads in [1, 2, 3] {
+            assert_eq!(calibrator.total_parallel(&document, threads), sequential);
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use crate::calibration::CalibrationError;
+
+// A written quantity: a spelled number phrase like "one hundred and five", or a run
+// of ASCII digits. `start` and `end` are byte offsets into the line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberToken {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+    pub value: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberTooLarge {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Word {
+    Zero,
+    Unit(u64),
+    Teen(u64),
+    Tens(u64),
+    Hundred,
+    Scale(u64),
+    And,
+}
+
+const UNITS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+const TEENS: [&str; 10] = [
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [(&str, u64); 3] = [
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+];
+
+fn classify(word: &str) -> Option<Word> {
+    let word = word.to_lowercase();
+    let position = |table: &[&str]| table.iter().position(|entry| *entry == word);
+
+    if word == "zero" {
+        Some(Word::Zero)
+    } else if word == "hundred" {
+        Some(Word::Hundred)
+    } else if word == "and" {
+        Some(Word::And)
+    } else if let Some(index) = position(&UNITS) {
+        Some(Word::Unit(index as u64 + 1))
+    } else if let Some(index) = position(&TEENS) {
+        Some(Word::Teen(index as u64 + 10))
+    } else if let Some(index) = position(&TENS) {
+        Some(Word::Tens(index as u64 * 10 + 20))
+    } else {
+        SCALES
+            .iter()
+            .find(|(name, _)| *name == word)
+            .map(|&(_, scale)| Word::Scale(scale))
+    }
+}
+
+// The value of a phrase read so far. `group` is the part below the last scale word,
+// e.g. 105 in "two thousand one hundred and five".
+struct Phrase {
+    total: u64,
+    group: u64,
+    last: Option<Word>,
+    last_scale: u64,
+}
+
+impl Phrase {
+    fn new() -> Phrase {
+        Phrase {
+            total: 0,
+            group: 0,
+            last: None,
+            last_scale: u64::MAX,
+        }
+    }
+
+    fn value(&self) -> u64 {
+        self.total + self.group
+    }
+
+    // Adds a word when it can continue the phrase, e.g. "five" after "twenty" but not
+    // after "fifteen". Scales have to get smaller from left to right, as in
+    // "two million three thousand".
+    fn push(&mut self, word: Word) -> bool {
+        use Word::*;
+
+        let allowed = match (self.last, word) {
+            (None, Zero) => true,
+            (Some(Zero), _) | (_, Zero) => false,
+            (None | Some(Hundred | Scale(_) | And), Unit(_) | Teen(_) | Tens(_)) => true,
+            (Some(Tens(_)), Unit(_)) => true,
+            (Some(Unit(_) | Teen(_)), Hundred) => self.group < 100,
+            (Some(Unit(_) | Teen(_) | Tens(_) | Hundred), Scale(scale)) => scale < self.last_scale,
+            (Some(Hundred | Scale(_)), And) => true,
+            _ => false,
+        };
+        if !allowed {
+            return false;
+        }
+
+        match word {
+            Unit(value) | Teen(value) | Tens(value) => self.group += value,
+            Hundred => self.group *= 100,
+            Scale(scale) => {
+                self.total += self.group * scale;
+                self.group = 0;
+                self.last_scale = scale;
+            }
+            Zero | And => {}
+        }
+        self.last = Some(word);
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Piece {
+    Letters,
+    Digits,
+}
+
+// Runs of letters and runs of ASCII digits, with their byte ranges.
+fn pieces(line: &str) -> Vec<(Piece, usize, usize)> {
+    let mut pieces: Vec<(Piece, usize, usize)> = Vec::new();
+
+    for (index, c) in line.char_indices() {
+        let piece = if c.is_alphabetic() {
+            Piece::Letters
+        } else if c.is_ascii_digit() {
+            Piece::Digits
+        } else {
+            continue;
+        };
+
+        match pieces.last_mut() {
+            Some((last, _, end)) if *last == piece && *end == index => *end += c.len_utf8(),
+            _ => pieces.push((piece, index, index + c.len_utf8())),
+        }
+    }
+
+    pieces
+}
+
+// Words of one phrase may only be separated by spaces and hyphens ("twenty-one").
+fn joined(line: &str, end: usize, start: usize) -> bool {
+    start > end && line[end..start].chars().all(|c| c == ' ' || c == '-')
+}
+
+// Every written quantity of an English line, left to right. Phrases are made of
+// whole words, so "zoneight" holds no number here.
+pub fn number_tokens(line: &str) -> Result<Vec<NumberToken>, NumberTooLarge> {
+    let pieces = pieces(line);
+    let mut tokens = Vec::new();
+    let token = |start: usize, end: usize, value: u64| NumberToken {
+        start,
+        end,
+        text: line[start..end].to_string(),
+        value,
+    };
+
+    let mut index = 0;
+    while index < pieces.len() {
+        let (piece, start, end) = pieces[index];
+        if piece == Piece::Digits {
+            let value = line[start..end]
+                .parse()
+                .map_err(|_| NumberTooLarge { start, end })?;
+            tokens.push(token(start, end, value));
+            index += 1;
+            continue;
+        }
+
+        let mut phrase = Phrase::new();
+        let mut last_word = None;
+        let mut next = index;
+        while let Some(&(Piece::Letters, word_start, word_end)) = pieces.get(next) {
+            if next > index && !joined(line, pieces[next - 1].2, word_start) {
+                break;
+            }
+            match classify(&line[word_start..word_end]) {
+                Some(word) if phrase.push(word) => {
+                    // a trailing "and" isn't part of the phrase
+                    if word != Word::And {
+                        last_word = Some((next, phrase.value()));
+                    }
+                }
+                _ => break,
+            }
+            next += 1;
+        }
+
+        match last_word {
+            Some((last, value)) => {
+                tokens.push(token(start, pieces[last].2, value));
+                index = last + 1;
+            }
+            None => index += 1,
+        }
+    }
+
+    Ok(tokens)
+}
+
+// Sums every written quantity of the document, with the same overflow checks as
+// calibration totals.
+pub fn quantity_total<I, S>(lines: I) -> Result<u64, CalibrationError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut total: u64 = 0;
+
+    for (index, line) in lines.into_iter().enumerate() {
+        let line_number = index + 1;
+        let tokens =
+            number_tokens(line.as_ref()).map_err(|err| CalibrationError::ValueOverflow {
+                line_number,
+                digits: err.end - err.start,
+            })?;
+
+        for token in tokens {
+            total = total
+                .checked_add(token.value)
+                .ok_or(CalibrationError::TotalOverflow {
+                    line_number,
+                    total,
+                    value: token.value,
+                })?;
+        }
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(line: &str) -> Vec<u64> {
+        number_tokens(line)
+            .unwrap()
+            .iter()
+            .map(|token| token.value)
+            .collect()
+    }
+
+    #[test]
+    fn test_phrases() {
+        assert_eq!(values("seventeen"), vec![17]);
+        assert_eq!(values("twenty-one"), vec![21]);
+        assert_eq!(values("one hundred and five"), vec![105]);
+        assert_eq!(values("Two Thousand and Twenty-Three"), vec![2023]);
+        assert_eq!(values("fifteen hundred"), vec![1500]);
+        assert_eq!(
+            values("three million four hundred thousand and nine"),
+            vec![3_400_009]
+        );
+        assert_eq!(values("zero"), vec![0]);
+    }
+
+    #[test]
+    fn test_phrase_boundaries() {
+        assert_eq!(values("one two"), vec![1, 2]);
+        assert_eq!(values("twenty, one"), vec![20, 1]);
+        assert_eq!(values("fifteen five"), vec![15, 5]);
+        assert_eq!(values("one hundred and"), vec![100]);
+        assert_eq!(values("two hundred hundred"), vec![200]);
+        assert_eq!(values("zoneight and hundred"), vec![]);
+        assert_eq!(values("zero one"), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_token_spans() {
+        let tokens = number_tokens("I paid twenty-one pounds for 3 and sixty.").unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                NumberToken {
+                    start: 7,
+                    end: 17,
+                    text: "twenty-one".to_string(),
+                    value: 21
+                },
+                NumberToken {
+                    start: 29,
+                    end: 30,
+                    text: "3".to_string(),
+                    value: 3
+                },
+                NumberToken {
+                    start: 35,
+                    end: 40,
+                    text: "sixty".to_string(),
+                    value: 60
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quantity_total() {
+        let document = "one hundred and five apples\nseventeen pears, 3 plums\nnothing";
+
+        assert_eq!(quantity_total(document.lines()), Ok(105 + 17 + 3));
+        assert_eq!(
+            quantity_total(["1", "123456789012345678901234"]),
+            Err(CalibrationError::ValueOverflow {
+                line_number: 2,
+                digits: 24
+            })
+        );
+        assert_eq!(
+            quantity_total(["10000000000000000000", "and 10000000000000000000"]),
+            Err(CalibrationError::TotalOverflow {
+                line_number: 2,
+                total: 10_000_000_000_000_000_000,
+                value: 10_000_000_000_000_000_000
+            })
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use aho_corasick::automaton::Automaton;
+use aho_corasick::dfa::DFA;
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, Anchored, MatchKind};
+
+use crate::unicode::UnicodePolicy;
+use crate::vocabulary::Vocabulary;
+
+// A digit found in a line, with the byte range of the text it was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitToken {
+    pub start: usize,
+    pub end: usize,
+    pub digit: u8,
+}
+
+// Finds the numeric and spelled digits of a line in one pass over it. The automata
+// are compiled once per vocabulary and can then scan any number of lines.
+#[derive(Debug, Clone)]
+pub struct DigitScanner {
+    automaton: AhoCorasick,
+    leftmost: AhoCorasick,
+    // matches the reversed patterns, for walking a line from its end
+    reverse: DFA,
+    digits: Vec<u8>,
+}
+
+// The automaton only folds ASCII case by itself, so every mix of lower and upper
+// case for the non-ASCII letters of a word gets its own pattern ("fünf", "fÜnf").
+fn case_variants(word: &str) -> Vec<String> {
+    let mut variants = vec![String::new()];
+
+    for c in word.chars() {
+        if c.is_ascii() {
+            variants.iter_mut().for_each(|variant| variant.push(c));
+            continue;
+        }
+
+        let lower: String = c.to_lowercase().collect();
+        let upper: String = c.to_uppercase().collect();
+        variants = variants
+            .into_iter()
+            .flat_map(|variant| {
+                let mut cased = vec![variant.clone() + &lower];
+                if upper != lower {
+                    cased.push(variant + &upper);
+                }
+                cased
+            })
+            .collect();
+    }
+
+    variants
+}
+
+impl DigitScanner {
+    pub fn new(vocabulary: &Vocabulary, policy: UnicodePolicy) -> DigitScanner {
+        let mut patterns = Vec::new();
+        let mut digits = Vec::new();
+
+        for (c, digit) in policy.digit_chars() {
+            patterns.push(c.to_string());
+            digits.push(digit);
+        }
+        for (word, digit) in vocabulary.words() {
+            for variant in case_variants(word) {
+                patterns.push(variant);
+                digits.push(digit);
+            }
+        }
+
+        let automaton = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::Standard)
+            .ascii_case_insensitive(true)
+            .build(&patterns)
+            .expect("Could not build the digit automaton");
+        let leftmost = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::LeftmostLongest)
+            .ascii_case_insensitive(true)
+            .build(&patterns)
+            .expect("Could not build the digit automaton");
+        let reversed: Vec<Vec<u8>> = patterns
+            .iter()
+            .map(|pattern| pattern.bytes().rev().collect())
+            .collect();
+        let reverse = DFA::builder()
+            .match_kind(MatchKind::Standard)
+            .ascii_case_insensitive(true)
+            .build(&reversed)
+            .expect("Could not build the reverse digit automaton");
+
+        DigitScanner {
+            automaton,
+            leftmost,
+            reverse,
+            digits,
+        }
+    }
+
+    // Every digit of the line in order, overlapping ones included ("zoneight" gives
+    // 1 and 8). When several words start at the same place the longest one counts.
+    pub fn tokens(&self, line: &str) -> Vec<DigitToken> {
+        let mut tokens: Vec<DigitToken> = self
+            .automaton
+            .find_overlapping_iter(line)
+            .map(|found| DigitToken {
+                start: found.start(),
+                end: found.end(),
+                digit: self.digits[found.pattern().as_usize()],
+            })
+            .collect();
+
+        tokens.sort_by_key(|token| (token.start, std::cmp::Reverse(token.end)));
+        tokens.dedup_by_key(|token| token.start);
+        tokens
+    }
+
+    // The token `tokens` would list first, found without looking past it.
+    pub fn first(&self, line: &str) -> Option<DigitToken> {
+        self.leftmost.find(line).map(|found| DigitToken {
+            start: found.start(),
+            end: found.end(),
+            digit: self.digits[found.pattern().as_usize()],
+        })
+    }
+
+    // The token `tokens` would list last. The line is fed backwards to the reverse
+    // automaton, so its first match is the token that starts furthest right.
+    pub fn last(&self, line: &str) -> Option<DigitToken> {
+        let bytes = line.as_bytes();
+        let dfa = &self.reverse;
+        let mut state = dfa
+            .start_state(Anchored::No)
+            .expect("Unanchored searches are supported");
+
+        for start in (0..bytes.len()).rev() {
+            state = dfa.next_state(Anchored::No, state, bytes[start]);
+            if !dfa.is_special(state) || !dfa.is_match(state) {
+                continue;
+            }
+
+            let pattern = (0..dfa.match_len(state))
+                .map(|index| dfa.match_pattern(state, index))
+                .max_by_key(|&pattern| dfa.pattern_len(pattern))?;
+            return Some(DigitToken {
+                start,
+                end: start + dfa.pattern_len(pattern),
+                digit: self.digits[pattern.as_usize()],
+            });
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_variants() {
+        assert_eq!(case_variants("one"), vec!["one"]);
+        assert_eq!(case_variants("fünf"), vec!["fünf", "fÜnf"]);
+        assert_eq!(case_variants("één").len(), 4);
+    }
+
+    #[test]
+    fn test_overlapping_tokens() {
+        let scanner = DigitScanner::new(&Vocabulary::english(), UnicodePolicy::AsciiOnly);
+
+        assert_eq!(
+            scanner.tokens("zoneight2"),
+            vec![
+                DigitToken {
+                    start: 1,
+                    end: 4,
+                    digit: 1
+                },
+                DigitToken {
+                    start: 3,
+                    end: 8,
+                    digit: 8
+                },
+                DigitToken {
+                    start: 8,
+                    end: 9,
+                    digit: 2
+                },
+            ]
+        );
+        assert_eq!(scanner.tokens("no digits here"), vec![]);
+    }
+
+    #[test]
+    fn test_first_and_last_tokens() {
+        let scanner = DigitScanner::new(&Vocabulary::english(), UnicodePolicy::AsciiOnly);
+
+        assert_eq!(
+            scanner.first("zoneight2"),
+            Some(DigitToken {
+                start: 1,
+                end: 4,
+                digit: 1
+            })
+        );
+        assert_eq!(
+            scanner.last("7pqrstsixteen"),
+            Some(DigitToken {
+                start: 6,
+                end: 9,
+                digit: 6
+            })
+        );
+        assert_eq!(scanner.last("xtwonE"), scanner.tokens("xtwonE").pop());
+        assert_eq!(scanner.first("no digits here"), None);
+        assert_eq!(scanner.last("no digits here"), None);
+    }
+
+    #[test]
+    fn test_first_and_last_match_tokens() {
+        let vocabulary = Vocabulary::new(
+            "custom",
+            &[("ein", 1), ("eins", 2), ("nsa", 3), ("fünf", 5)],
+        );
+        let scanner = DigitScanner::new(&vocabulary, UnicodePolicy::AsciiOnly);
+        let lines = ["einsa", "xeinsam", "FÜNFeins", "nsanein", "4einsfünf", "ü"];
+
+        for line in lines {
+            let tokens = scanner.tokens(line);
+            assert_eq!(scanner.first(line), tokens.first().copied(), "{}", line);
+            assert_eq!(scanner.last(line), tokens.last().copied(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_scanner_is_reusable() {
+        let scanner = DigitScanner::new(&Vocabulary::german(), UnicodePolicy::AsciiOnly);
+        let lines = ["FÜNFzehn", "siebenacht", "x"];
+
+        let digits: Vec<Vec<u8>> = lines
+            .iter()
+            .map(|line| scanner.tokens(line).iter().map(|t| t.digit).collect())
+            .collect();
+
+        assert_eq!(digits, vec![vec![5], vec![7, 8], vec![]]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+
+// What to do with numeric characters outside of ASCII, like '½', '٣' or '３'.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum UnicodePolicy {
+    // only '0'..='9' are digits, everything else is ignored
+    #[default]
+    #[value(name = "ascii")]
+    AsciiOnly,
+    // any decimal digit (general category Nd) counts, with its digit value
+    #[value(name = "decimal")]
+    DecimalDigits,
+    // only '0'..='9' are digits, and lines with other numeric characters are an error
+    #[value(name = "reject")]
+    Reject,
+}
+
+// The zero of every run of decimal digits in Unicode 15. Each run holds the
+// digits 0 to 9 at consecutive code points.
+const DECIMAL_ZEROS: [u32; 68] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0,
+    0x11650, 0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16A60,
+    0x16AC0, 0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0,
+    0x1E950, 0x1FBF0,
+];
+
+// The value of a character in the Nd category, e.g. 3 for '٣'.
+pub fn decimal_value(c: char) -> Option<u8> {
+    let code = c as u32;
+    let run = match DECIMAL_ZEROS.binary_search(&code) {
+        Ok(index) => index,
+        Err(0) => return None,
+        Err(index) => index - 1,
+    };
+
+    let value = code - DECIMAL_ZEROS[run];
+    (value < 10).then_some(value as u8)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnicodeError {
+    pub characters: Vec<char>,
+}
+
+impl fmt::Display for UnicodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let characters: Vec<String> = self
+            .characters
+            .iter()
+            .map(|c| format!("{:?} (U+{:04X})", c, *c as u32))
+            .collect();
+        write!(
+            f,
+            "unsupported numeric characters {}",
+            characters.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnicodeError {}
+
+impl UnicodePolicy {
+    pub fn digit(&self, c: char) -> Option<u8> {
+        match self {
+            UnicodePolicy::AsciiOnly | UnicodePolicy::Reject => {
+                c.is_ascii_digit().then(|| c as u8 - b'0')
+            }
+            UnicodePolicy::DecimalDigits => decimal_value(c),
+        }
+    }
+
+    // Every character the policy reads as a digit.
+    pub fn digit_chars(&self) -> Vec<(char, u8)> {
+        let zeros: &[u32] = match self {
+            UnicodePolicy::AsciiOnly | UnicodePolicy::Reject => &DECIMAL_ZEROS[..1],
+            UnicodePolicy::DecimalDigits => &DECIMAL_ZEROS,
+        };
+
+        zeros
+            .iter()
+            .flat_map(|zero| (0..10).map(move |value| (zero + value as u32, value)))
+            .filter_map(|(code, value)| Some((char::from_u32(code)?, value)))
+            .collect()
+    }
+
+    // Under `Reject`, lists every distinct numeric character of the line that isn't
+    // an ASCII digit. The other policies accept any line.
+    pub fn check(&self, line: &str) -> Result<(), UnicodeError> {
+        if *self != UnicodePolicy::Reject {
+            return Ok(());
+        }
+
+        let mut characters = Vec::new();
+        for c in line.chars() {
+            if c.is_numeric() && !c.is_ascii_digit() && !characters.contains(&c) {
+                characters.push(c);
+            }
+        }
+
+        if characters.is_empty() {
+            Ok(())
+        } else {
+            Err(UnicodeError { characters })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_value() {
+        assert_eq!(decimal_value('7'), Some(7));
+        assert_eq!(decimal_value('٣'), Some(3));
+        assert_eq!(decimal_value('３'), Some(3));
+        assert_eq!(decimal_value('𝟗'), Some(9));
+        assert_eq!(decimal_value('½'), None);
+        assert_eq!(decimal_value('五'), None);
+        assert_eq!(decimal_value('a'), None);
+        assert_eq!(decimal_value('\u{0670}'), None);
+    }
+
+    #[test]
+    fn test_decimal_table_matches_std() {
+        for (c, value) in UnicodePolicy::DecimalDigits.digit_chars() {
+            assert!(c.is_numeric(), "{:?}", c);
+            assert_eq!(decimal_value(c), Some(value));
+        }
+        assert_eq!(UnicodePolicy::AsciiOnly.digit_chars().len(), 10);
+        assert_eq!(UnicodePolicy::DecimalDigits.digit_chars().len(), 680);
+    }
+
+    #[test]
+    fn test_policy_digit() {
+        assert_eq!(UnicodePolicy::AsciiOnly.digit('٣'), None);
+        assert_eq!(UnicodePolicy::AsciiOnly.digit('3'), Some(3));
+        assert_eq!(UnicodePolicy::DecimalDigits.digit('٣'), Some(3));
+        assert_eq!(UnicodePolicy::Reject.digit('٣'), None);
+    }
+
+    #[test]
+    fn test_reject_lists_characters() {
+        let err = UnicodePolicy::Reject.check("1½a٣½五").unwrap_err();
+
+        assert_eq!(err.characters, vec!['½', '٣']);
+        assert_eq!(
+            err.to_string(),
+            "unsupported numeric characters '½' (U+00BD), '٣' (U+0663)"
+        );
+        assert_eq!(UnicodePolicy::Reject.check("a1b2"), Ok(()));
+        assert_eq!(UnicodePolicy::AsciiOnly.check("½"), Ok(()));
+    }
+}
@@ -0,0 +1,268 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// The spelled-out digits of one language. Words are stored lowercased and
+// matched case-insensitively, so "Seven" and "SEVEN" both count as 7.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    pub name: String,
+    words: Vec<(String, u8)>,
+}
+
+const ENGLISH: [(&str, u8); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+const SPANISH: [(&str, u8); 10] = [
+    ("cero", 0),
+    ("uno", 1),
+    ("dos", 2),
+    ("tres", 3),
+    ("cuatro", 4),
+    ("cinco", 5),
+    ("seis", 6),
+    ("siete", 7),
+    ("ocho", 8),
+    ("nueve", 9),
+];
+
+const GERMAN: [(&str, u8); 10] = [
+    ("null", 0),
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+const FRENCH: [(&str, u8); 10] = [
+    ("zéro", 0),
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+
+pub const BUILTIN_LANGUAGES: [&str; 4] = ["english", "spanish", "german", "french"];
+
+#[derive(Debug)]
+pub enum VocabularyError {
+    Io(PathBuf, io::Error),
+    InvalidLine { line_number: usize, line: String },
+    UnknownLanguage(String),
+    Empty,
+}
+
+impl fmt::Display for VocabularyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VocabularyError::Io(path, err) => {
+                write!(f, "could not read {}: {}", path.display(), err)
+            }
+            VocabularyError::InvalidLine { line_number, line } => write!(
+                f,
+                "line {}: expected `word = digit`, got {:?}",
+                line_number, line
+            ),
+            VocabularyError::UnknownLanguage(language) => write!(
+                f,
+                "unknown language {:?}, expected one of {}",
+                language,
+                BUILTIN_LANGUAGES.join(", ")
+            ),
+            VocabularyError::Empty => write!(f, "vocabulary has no words"),
+        }
+    }
+}
+
+impl std::error::Error for VocabularyError {}
+
+impl Vocabulary {
+    pub fn new(name: &str, words: &[(&str, u8)]) -> Vocabulary {
+        let words = words
+            .iter()
+            .map(|&(word, digit)| (word.to_lowercase(), digit))
+            .collect();
+
+        Vocabulary {
+            name: name.to_string(),
+            words,
+        }
+    }
+
+    pub fn english() -> Vocabulary {
+        Vocabulary::new("english", &ENGLISH)
+    }
+
+    pub fn spanish() -> Vocabulary {
+        Vocabulary::new("spanish", &SPANISH)
+    }
+
+    pub fn german() -> Vocabulary {
+        Vocabulary::new("german", &GERMAN)
+    }
+
+    pub fn french() -> Vocabulary {
+        Vocabulary::new("french", &FRENCH)
+    }
+
+    pub fn builtin(language: &str) -> Result<Vocabulary, VocabularyError> {
+        match language.to_lowercase().as_str() {
+            "english" | "en" => Ok(Vocabulary::english()),
+            "spanish" | "es" => Ok(Vocabulary::spanish()),
+            "german" | "de" => Ok(Vocabulary::german()),
+            "french" | "fr" => Ok(Vocabulary::french()),
+            _ => Err(VocabularyError::UnknownLanguage(language.to_string())),
+        }
+    }
+
+    // Custom vocabularies have one `word = digit` pair per line. Blank lines and
+    // lines starting with `#` are skipped.
+    pub fn parse(name: &str, text: &str) -> Result<Vocabulary, VocabularyError> {
+        let mut words = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || VocabularyError::InvalidLine {
+                line_number: index + 1,
+                line: line.to_string(),
+            };
+            let (word, digit) = line.split_once('=').ok_or_else(invalid)?;
+            let word = word.trim();
+            let digit = digit.trim().parse::<u8>().map_err(|_| invalid())?;
+            if word.is_empty() || digit > 9 {
+                return Err(invalid());
+            }
+
+            words.push((word, digit));
+        }
+
+        if words.is_empty() {
+            return Err(VocabularyError::Empty);
+        }
+
+        Ok(Vocabulary::new(name, &words))
+    }
+
+    pub fn from_file(path: &Path) -> Result<Vocabulary, VocabularyError> {
+        let text =
+            fs::read_to_string(path).map_err(|err| VocabularyError::Io(path.to_path_buf(), err))?;
+        let name = path
+            .file_stem()
+            .map_or("custom".into(), |stem| stem.to_string_lossy());
+
+        Vocabulary::parse(&name, &text)
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u8)> {
+        self.words
+            .iter()
+            .map(|(word, digit)| (word.as_str(), *digit))
+    }
+
+    // A single ASCII digit, or one of the vocabulary's words in any case.
+    pub fn digit_for(&self, text: &str) -> Option<u8> {
+        if let [digit @ b'0'..=b'9'] = text.as_bytes() {
+            return Some(digit - b'0');
+        }
+
+        let text = text.to_lowercase();
+        self.words
+            .iter()
+            .find(|(word, _)| *word == text)
+            .map(|&(_, digit)| digit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digit_for() {
+        let english = Vocabulary::english();
+
+        assert_eq!(english.digit_for("1"), Some(1));
+        assert_eq!(english.digit_for("1234"), None);
+        assert_eq!(english.digit_for("one"), Some(1));
+        assert_eq!(english.digit_for("Two"), Some(2));
+        assert_eq!(english.digit_for("ZERO"), Some(0));
+        assert_eq!(english.digit_for("notanumber"), None);
+    }
+
+    #[test]
+    fn test_builtin_vocabularies() {
+        assert_eq!(Vocabulary::spanish().digit_for("Nueve"), Some(9));
+        assert_eq!(Vocabulary::german().digit_for("FÜNF"), Some(5));
+        assert_eq!(Vocabulary::french().digit_for("zéro"), Some(0));
+        assert_eq!(Vocabulary::french().digit_for("one"), None);
+        assert_eq!(Vocabulary::builtin("DE").unwrap(), Vocabulary::german());
+        assert_eq!(
+            Vocabulary::builtin("klingon").unwrap_err().to_string(),
+            "unknown language \"klingon\", expected one of english, spanish, german, french"
+        );
+    }
+
+    #[test]
+    fn test_parse_vocabulary() {
+        let text = "# Dutch\nnul = 0\nÉén=1\n\n  twee = 2  \n";
+        let vocabulary = Vocabulary::parse("dutch", text).unwrap();
+
+        assert_eq!(
+            vocabulary.words().collect::<Vec<_>>(),
+            vec![("nul", 0), ("één", 1), ("twee", 2)]
+        );
+        assert!(matches!(
+            Vocabulary::parse("bad", "nul = 0\ntien = 10"),
+            Err(VocabularyError::InvalidLine { line_number: 2, .. })
+        ));
+        assert!(matches!(
+            Vocabulary::parse("bad", "nul 0"),
+            Err(VocabularyError::InvalidLine { line_number: 1, .. })
+        ));
+        assert!(matches!(
+            Vocabulary::parse("bad", "# nothing\n"),
+            Err(VocabularyError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_vocabulary_from_file() {
+        let path = std::env::temp_dir().join(format!("dutch-{}.txt", std::process::id()));
+        fs::write(&path, "een = 1\ntwee = 2\n").unwrap();
+
+        let vocabulary = Vocabulary::from_file(&path).unwrap();
+        assert_eq!(vocabulary.name, format!("dutch-{}", std::process::id()));
+        assert_eq!(vocabulary.digit_for("TWEE"), Some(2));
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            Vocabulary::from_file(&path),
+            Err(VocabularyError::Io(..))
+        ));
+    }
+}
@@ -0,0 +1,31 @@
+use clap::Args;
+use day2::export::{self, Format};
+use runner::Runner;
+
+use std::process;
+
+#[derive(Args, Debug)]
+struct ConvertArgs {
+    /// Format of the input log
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    from: Format,
+
+    /// Format to print the games in
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    to: Format,
+}
+
+// Reads a game log and prints it in canonical form, or in another format.
+fn main() {
+    let (runner, args) = Runner::from_args_with::<ConvertArgs>();
+    let input = runner.load_input(env!("CARGO_MANIFEST_DIR"));
+
+    let converted = export::read(&input, args.from)
+        .and_then(|games| export::write(&games, args.to))
+        .unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            process::exit(1);
+        });
+
+    print!("{}", converted);
+}
@@ -0,0 +1,61 @@
+use clap::Args;
+use day2::estimate::{self, BagEstimate, BagSize};
+use day2::game;
+use runner::Runner;
+
+use std::process;
+
+#[derive(Args, Debug)]
+struct EstimateArgs {
+    /// Only consider bags holding exactly this many cubes
+    #[arg(long, value_name = "N")]
+    total: Option<u32>,
+
+    /// Largest bag to consider when the total isn't fixed
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 100,
+        value_parser = clap::value_parser!(u32).range(..=estimate::MAX_SEARCHED_TOTAL as i64),
+        conflicts_with = "total"
+    )]
+    max_total: u32,
+
+    /// Also estimate a bag for every game on its own
+    #[arg(long)]
+    per_game: bool,
+}
+
+fn describe(estimate: Option<BagEstimate>) -> String {
+    match estimate {
+        Some(estimate) => format!(
+            "{} (log-likelihood {:.3})",
+            estimate.bag, estimate.log_likelihood
+        ),
+        None => "no bag of that size fits".to_string(),
+    }
+}
+
+// Prints the most likely bag for the whole log, and optionally for every game.
+fn main() {
+    let (runner, args) = Runner::from_args_with::<EstimateArgs>();
+    let input = runner.load_input(env!("CARGO_MANIFEST_DIR"));
+    let games = game::parse_game_file(input.lines()).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+    let size = match args.total {
+        Some(total) => BagSize::Exactly(total),
+        None => BagSize::AtMost(args.max_total),
+    };
+
+    if args.per_game {
+        for game in &games {
+            println!("Game {}: {}", game.id, describe(game.estimate_bag(size)));
+        }
+    }
+    println!(
+        "All games: {}",
+        describe(estimate::estimate_common_bag(&games, size))
+    );
+}
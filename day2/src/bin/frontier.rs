@@ -0,0 +1,43 @@
+use clap::Args;
+use day2::frontier::frontier;
+use day2::game;
+use runner::Runner;
+
+use std::process;
+
+#[derive(Args, Debug)]
+struct FrontierArgs {
+    /// Only show bags with at most this many cubes
+    #[arg(long, value_name = "N")]
+    max_total: Option<u32>,
+
+    /// Only show bags making at least this fraction of the games possible
+    #[arg(long, value_name = "FRACTION", default_value_t = 0.0)]
+    min_fraction: f64,
+}
+
+// Prints the smallest bags making more and more games possible.
+fn main() {
+    let (runner, args) = Runner::from_args_with::<FrontierArgs>();
+    let input = runner.load_input(env!("CARGO_MANIFEST_DIR"));
+    let games = game::parse_game_file(input.lines()).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+
+    let frontier = frontier(&games);
+    let points = frontier.query(args.max_total.unwrap_or(u32::MAX), args.min_fraction);
+    if points.is_empty() {
+        println!("No bag matches");
+    }
+    for point in points {
+        println!(
+            "{:>4} cubes: {:>4} of {} games ({:.1}%) with {}",
+            point.size,
+            point.possible,
+            frontier.games,
+            frontier.fraction(point) * 100.0,
+            point.bag
+        );
+    }
+}
@@ -1,25 +1,45 @@
-use day2::game;
+use day2::cli::GameArgs;
+use day2::cubes::CubeSet;
+use runner::Runner;
 
-fn parse_game_file(game_data: &str) -> Vec<game::Game> {
-    let mut games: Vec<game::Game> = Vec::new();
+use std::process;
 
-    for line in game_data.lines() {
-        let game = game::Game::from_game_line(&line);
-        games.push(game);
-    }
-
-    games
+fn sum_ids(ids: impl IntoIterator<Item = u32>) -> u64 {
+    ids.into_iter()
+        .try_fold(0u64, |sum, id| sum.checked_add(id as u64))
+        .unwrap_or_else(|| {
+            eprintln!("The sum of the game ids doesn't fit in a u64");
+            process::exit(1);
+        })
 }
 
 fn main() {
-    let data = include_str!("../data.txt");
-    let games = parse_game_file(data);
-    let world = (12, 13, 14);
+    let (runner, args) = Runner::from_args_with::<GameArgs>();
+    let filter = args.filter();
+    let mut input = runner.open_input(env!("CARGO_MANIFEST_DIR"));
+    let lines = input
+        .lines()
+        .map(|line| line.expect("Could not read puzzle input"));
+    let games = args.games(lines);
+
+    if let Some(filter) = filter {
+        let ids: Vec<u32> = games
+            .filter(|game| filter.matches(game))
+            .map(|game| game.id)
+            .collect();
+        let listed: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        println!("Matching games: {}", listed.join(", "));
+        println!("Sum of matching ids: {}", sum_ids(ids));
+        return;
+    }
+
+    let world = CubeSet::rgb(12, 13, 14);
 
-    let possible_games_sum = games
-        .iter()
-        .filter(|game| game.is_possible(world))
-        .fold(0, |sum, game| sum + game.id);
+    let possible_games_sum = sum_ids(
+        games
+            .filter(|game| game.is_possible(&world))
+            .map(|game| game.id),
+    );
 
     println!("Possible games: {}", possible_games_sum);
 }
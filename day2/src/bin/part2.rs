@@ -1,26 +1,30 @@
-use day2::game;
+use day2::cli::GameArgs;
+use day2::cubes::RGB;
+use runner::Runner;
 
-fn parse_game_file(game_data: &str) -> Vec<game::Game> {
-    let mut games: Vec<game::Game> = Vec::new();
-
-    for line in game_data.lines() {
-        let game = game::Game::from_game_line(&line);
-        games.push(game);
-    }
-
-    games
-}
+use std::process;
 
 fn main() {
-		let data = include_str!("../data.txt");
-    let games = parse_game_file(data);
+    let (runner, args) = Runner::from_args_with::<GameArgs>();
+    let filter = args.filter();
+    let mut input = runner.open_input(env!("CARGO_MANIFEST_DIR"));
+    let lines = input
+        .lines()
+        .map(|line| line.expect("Could not read puzzle input"));
+    let games = args.games(lines);
 
-    let all_game_powers = games
-        .iter()
-        .map(|game| game.calc_min_world())
-        .map(|world| world.0 * world.1 * world.2)
-        .fold(0, |sum, power| sum + power);
+    let mut all_game_powers: u64 = 0;
+    for game in games.filter(|game| filter.as_ref().is_none_or(|filter| filter.matches(game))) {
+        all_game_powers = game
+            .calc_min_world()
+            .product_of(&RGB)
+            .and_then(|power| all_game_powers.checked_add(power))
+            .unwrap_or_else(|| {
+                eprintln!("The powers up to game {} don't fit in a u64", game.id);
+                process::exit(1);
+            });
+    }
 
     println!("Part 2!");
     println!("All game powers: {}", all_game_powers);
-}
\ No newline at end of file
+}
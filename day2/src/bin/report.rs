@@ -0,0 +1,44 @@
+use clap::{Args, ValueEnum};
+use day2::cubes::CubeSet;
+use day2::game;
+use day2::report::Report;
+use runner::Runner;
+
+use std::process;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Output {
+    Table,
+    Json,
+}
+
+#[derive(Args, Debug)]
+struct ReportArgs {
+    /// Bag to check the games against, written like a turn
+    #[arg(long, default_value = "12 red, 13 green, 14 blue")]
+    bag: String,
+
+    /// How to print the report
+    #[arg(long, value_enum, default_value_t = Output::Table)]
+    format: Output,
+}
+
+// Prints descriptive statistics of a game log.
+fn main() {
+    let (runner, args) = Runner::from_args_with::<ReportArgs>();
+    let bag: CubeSet = args.bag.parse().unwrap_or_else(|err| {
+        eprintln!("Invalid bag: {}", err);
+        process::exit(1);
+    });
+    let input = runner.load_input(env!("CARGO_MANIFEST_DIR"));
+    let games = game::parse_game_file(input.lines()).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+
+    let report = Report::new(&games, &bag);
+    match args.format {
+        Output::Table => print!("{}", report.table()),
+        Output::Json => println!("{}", report.to_json()),
+    }
+}
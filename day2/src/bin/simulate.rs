@@ -0,0 +1,58 @@
+use clap::Parser;
+use day2::cubes::CubeSet;
+use day2::export::{self, Format};
+use day2::simulate::Simulator;
+
+use std::process;
+
+/// Prints a game log played with a known bag
+#[derive(Parser, Debug)]
+struct SimulateArgs {
+    /// Cubes in the bag, written like a turn
+    #[arg(long, default_value = "12 red, 13 green, 14 blue")]
+    bag: String,
+
+    /// Number of games to play
+    #[arg(long, default_value_t = 100)]
+    games: usize,
+
+    /// Turns in every game
+    #[arg(long, default_value_t = 3)]
+    turns: usize,
+
+    /// Fewest cubes drawn in a turn
+    #[arg(long, default_value_t = 1)]
+    min_draw: u32,
+
+    /// Most cubes drawn in a turn, the whole bag by default
+    #[arg(long)]
+    max_draw: Option<u32>,
+
+    /// Seed for the random draws; the same seed always gives the same log
+    #[arg(long, default_value_t = 2023)]
+    seed: u64,
+
+    /// Format to print the games in
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+fn main() {
+    let args = SimulateArgs::parse();
+    let bag: CubeSet = args.bag.parse().unwrap_or_else(|err| {
+        eprintln!("Invalid bag: {}", err);
+        process::exit(1);
+    });
+    let max_draw = args.max_draw.unwrap_or(bag.total());
+
+    let games = Simulator::new(bag, args.seed)
+        .with_turns(args.turns)
+        .with_draw_sizes(args.min_draw..=max_draw)
+        .games(args.games);
+    let log = export::write(&games, args.format).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+
+    print!("{}", log);
+}
@@ -0,0 +1,72 @@
+use std::process;
+
+use clap::Args;
+
+use crate::filter::Filter;
+use crate::game::{Game, GameParser};
+use crate::validation::DuplicatePolicy;
+
+// The options both parts read a game log with.
+#[derive(Args, Debug)]
+pub struct GameArgs {
+	/// Report lines that aren't valid games and skip them instead of stopping
+	#[arg(long)]
+	pub lenient: bool,
+
+	/// How to count a color that appears more than once in a turn
+	#[arg(long, value_enum, default_value_t = DuplicatePolicy::Sum)]
+	pub duplicates: DuplicatePolicy,
+
+	/// Reject lines showing more than this many cubes of one color in a turn
+	#[arg(long, value_name = "N")]
+	pub max_count: Option<u32>,
+
+	/// Only use the games matching this condition, e.g. "max(red) > 12 and any(blue == 0)"
+	#[arg(long, value_name = "EXPRESSION")]
+	pub filter: Option<String>,
+}
+
+impl GameArgs {
+	pub fn parser(&self) -> GameParser {
+		GameParser {
+			duplicates: self.duplicates,
+			max_count: self.max_count,
+		}
+	}
+
+	// The parsed --filter expression. Exits when it isn't valid.
+	pub fn filter(&self) -> Option<Filter> {
+		self.filter.as_deref().map(|expression| {
+			Filter::parse(expression).unwrap_or_else(|err| {
+				eprintln!("Invalid filter: {}", err);
+				process::exit(1);
+			})
+		})
+	}
+
+	// The games of a log, with their diagnostics printed as warnings. A line that
+	// isn't a game exits, unless --lenient is set and it is reported and skipped.
+	pub fn games<I, S>(&self, lines: I) -> impl Iterator<Item = Game>
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		let lenient = self.lenient;
+		self.parser().games(lines).filter_map(move |parsed| match parsed {
+			Ok(parsed) => {
+				for diagnostic in parsed.diagnostics {
+					eprintln!("Warning: {}", diagnostic);
+				}
+				Some(parsed.game)
+			}
+			Err(err) if lenient => {
+				eprintln!("Skipping {}", err);
+				None
+			}
+			Err(err) => {
+				eprintln!("{}", err);
+				process::exit(1);
+			}
+		})
+	}
+}
@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Add;
+
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
+
+pub const RGB: [&str; 3] = ["red", "green", "blue"];
+
+// Cube counts by color. Red, green and blue live in a fixed array since almost every
+// log only uses them; any other color goes in a map. Colors without cubes are left
+// out of the map, so two sets with the same counts always compare equal.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "BTreeMap<String, u32>")]
+pub struct CubeSet {
+	rgb: [u32; 3],
+	other: BTreeMap<String, u32>,
+}
+
+// Whether a game log can spell the color: the text parser only reads colors made of
+// letters, so anything else couldn't be written out and read back.
+pub fn is_color_name(color: &str) -> bool {
+	!color.is_empty() && color.chars().all(char::is_alphabetic)
+}
+
+fn rgb_index(color: &str) -> Option<usize> {
+	RGB.iter().position(|rgb| *rgb == color)
+}
+
+impl CubeSet {
+	pub fn new() -> CubeSet {
+		CubeSet::default()
+	}
+
+	pub fn rgb(red: u32, green: u32, blue: u32) -> CubeSet {
+		CubeSet {
+			rgb: [red, green, blue],
+			other: BTreeMap::new(),
+		}
+	}
+
+	pub fn count(&self, color: &str) -> u32 {
+		match rgb_index(color) {
+			Some(index) => self.rgb[index],
+			None => self.other.get(color).copied().unwrap_or(0),
+		}
+	}
+
+	pub fn set(&mut self, color: &str, count: u32) {
+		match rgb_index(color) {
+			Some(index) => self.rgb[index] = count,
+			None if count == 0 => {
+				self.other.remove(color);
+			}
+			None => {
+				self.other.insert(color.to_string(), count);
+			}
+		}
+	}
+
+	// Red, green and blue first, even when they have no cubes, then every other
+	// color in alphabetical order.
+	pub fn colors(&self) -> impl Iterator<Item = (&str, u32)> {
+		RGB.iter()
+			.copied()
+			.zip(self.rgb)
+			.chain(self.other.iter().map(|(color, count)| (color.as_str(), *count)))
+	}
+
+	// The component-wise maximum: the smallest set holding both.
+	pub fn max(&self, other: &CubeSet) -> CubeSet {
+		let mut max = self.clone();
+		for (color, count) in other.colors() {
+			if count > max.count(color) {
+				max.set(color, count);
+			}
+		}
+		max
+	}
+
+	// Colors that have cubes, in the same order as `colors`.
+	pub fn nonzero(&self) -> impl Iterator<Item = (&str, u32)> {
+		self.colors().filter(|(_, count)| *count > 0)
+	}
+
+	// Saturates rather than overflowing on absurd counts.
+	pub fn total(&self) -> u32 {
+		self.colors().fold(0, |total, (_, count)| total.saturating_add(count))
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.nonzero().next().is_none()
+	}
+
+	pub fn is_subset_of(&self, other: &CubeSet) -> bool {
+		self.colors().all(|(color, count)| count <= other.count(color))
+	}
+
+	// Multiplies the counts of the colors the set holds, so a log without red, green
+	// or blue still gets a power. An empty set has a product of 0. None when the
+	// product doesn't fit in a u64, which a handful of large counts is enough for.
+	pub fn product(&self) -> Option<u64> {
+		if self.is_empty() {
+			return Some(0);
+		}
+		checked_product(self.nonzero().map(|(_, count)| count))
+	}
+
+	// Multiplies the counts of the given colors, 0 when the set lacks one of them.
+	pub fn product_of(&self, colors: &[&str]) -> Option<u64> {
+		checked_product(colors.iter().map(|color| self.count(color)))
+	}
+}
+
+// A zero anywhere makes the product 0 even if the counts before it overflow.
+fn checked_product(counts: impl Iterator<Item = u32>) -> Option<u64> {
+	let counts: Vec<u64> = counts.map(u64::from).collect();
+	if counts.contains(&0) {
+		return Some(0);
+	}
+	counts.into_iter().try_fold(1u64, |product, count| product.checked_mul(count))
+}
+
+impl Add for CubeSet {
+	type Output = CubeSet;
+
+	// Saturates like `total`, a count can't go past u32::MAX.
+	fn add(mut self, other: CubeSet) -> CubeSet {
+		for (color, count) in other.colors() {
+			let sum = self.count(color).saturating_add(count);
+			self.set(color, sum);
+		}
+		self
+	}
+}
+
+impl<'a> FromIterator<(&'a str, u32)> for CubeSet {
+	fn from_iter<I: IntoIterator<Item = (&'a str, u32)>>(counts: I) -> CubeSet {
+		let mut set = CubeSet::new();
+		for (color, count) in counts {
+			set.set(color, count);
+		}
+		set
+	}
+}
+
+impl From<BTreeMap<String, u32>> for CubeSet {
+	fn from(counts: BTreeMap<String, u32>) -> CubeSet {
+		let mut set = CubeSet::new();
+		for (color, count) in counts {
+			set.set(&color.to_lowercase(), count);
+		}
+		set
+	}
+}
+
+// Serialized as a map of the colors that have cubes, e.g. {"red": 4, "blue": 3}.
+impl Serialize for CubeSet {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(None)?;
+		for (color, count) in self.nonzero() {
+			map.serialize_entry(color, &count)?;
+		}
+		map.end()
+	}
+}
+
+// The way a turn is written in a game log: "4 red, 3 blue". Colors without cubes are
+// left out, except in a turn with no cubes at all, which still has to say so.
+impl fmt::Display for CubeSet {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_empty() {
+			return write!(f, "0 red, 0 green, 0 blue");
+		}
+
+		let cubes: Vec<String> = self
+			.nonzero()
+			.map(|(color, count)| format!("{} {}", count, color))
+			.collect();
+		write!(f, "{}", cubes.join(", "))
+	}
+}
+
+impl fmt::Debug for CubeSet {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.colors()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_counts() {
+		let mut set = CubeSet::rgb(4, 0, 3);
+		set.set("purple", 2);
+
+		assert_eq!(set.count("red"), 4);
+		assert_eq!(set.count("green"), 0);
+		assert_eq!(set.count("purple"), 2);
+		assert_eq!(set.count("yellow"), 0);
+		assert_eq!(
+			set.colors().collect::<Vec<_>>(),
+			vec![("red", 4), ("green", 0), ("blue", 3), ("purple", 2)]
+		);
+
+		set.set("purple", 0);
+		assert_eq!(set, CubeSet::rgb(4, 0, 3));
+	}
+
+	#[test]
+	fn test_max() {
+		let a: CubeSet = [("red", 4), ("blue", 3), ("purple", 1)].into_iter().collect();
+		let b: CubeSet = [("red", 1), ("green", 2), ("yellow", 5)].into_iter().collect();
+
+		assert_eq!(
+			a.max(&b),
+			[("red", 4), ("green", 2), ("blue", 3), ("purple", 1), ("yellow", 5)]
+				.into_iter()
+				.collect()
+		);
+	}
+
+	#[test]
+	fn test_is_subset_of() {
+		let bag: CubeSet = [("red", 12), ("green", 13), ("blue", 14), ("purple", 2)]
+			.into_iter()
+			.collect();
+
+		assert!(CubeSet::rgb(12, 13, 14).is_subset_of(&bag));
+		assert!(!CubeSet::rgb(13, 0, 0).is_subset_of(&bag));
+		assert!([("purple", 2)].into_iter().collect::<CubeSet>().is_subset_of(&bag));
+		assert!(![("yellow", 1)].into_iter().collect::<CubeSet>().is_subset_of(&bag));
+		assert!(!bag.is_subset_of(&CubeSet::rgb(12, 13, 14)));
+	}
+
+	#[test]
+	fn test_add() {
+		let a: CubeSet = [("red", 4), ("purple", 1)].into_iter().collect();
+		let b: CubeSet = [("red", 1), ("blue", 2), ("purple", 2)].into_iter().collect();
+
+		assert_eq!(
+			a + b,
+			[("red", 5), ("blue", 2), ("purple", 3)].into_iter().collect()
+		);
+		assert_eq!(
+			CubeSet::rgb(u32::MAX, 1, 0) + CubeSet::rgb(1, 1, 0),
+			CubeSet::rgb(u32::MAX, 2, 0)
+		);
+	}
+
+	#[test]
+	fn test_product() {
+		let mut set = CubeSet::rgb(4, 2, 6);
+		assert_eq!(set.product(), Some(48));
+		assert_eq!(set.total(), 12);
+
+		set.set("purple", 3);
+		assert_eq!(set.product(), Some(144));
+		assert_eq!(set.product_of(&["purple", "red"]), Some(12));
+		assert_eq!(CubeSet::rgb(4, 0, 6).product(), Some(24));
+		assert_eq!(CubeSet::rgb(4, 0, 6).product_of(&RGB), Some(0));
+		assert_eq!(CubeSet::new().product(), Some(0));
+
+		let set: CubeSet = [("purple", 2), ("teal", 5)].into_iter().collect();
+		assert_eq!(set.product(), Some(10));
+	}
+
+	#[test]
+	fn test_product_overflow() {
+		let mut set = CubeSet::rgb(300, 300, 300);
+		for color in ["d", "e", "f", "g", "h"] {
+			set.set(color, 300);
+		}
+
+		assert_eq!(set.product(), None);
+		assert_eq!(set.product_of(&["red", "green", "blue"]), Some(27_000_000));
+
+		set.set("red", u32::MAX);
+		set.set("green", 0);
+		assert_eq!(set.product_of(&RGB), Some(0));
+	}
+
+	#[test]
+	fn test_display() {
+		let set: CubeSet = [("purple", 2), ("blue", 3), ("red", 4)].into_iter().collect();
+
+		assert_eq!(set.to_string(), "4 red, 3 blue, 2 purple");
+		assert_eq!(CubeSet::new().to_string(), "0 red, 0 green, 0 blue");
+	}
+
+	#[test]
+	fn test_serde() {
+		let set: CubeSet = [("purple", 2), ("blue", 3), ("red", 4)].into_iter().collect();
+		let json = serde_json::to_string(&set).unwrap();
+
+		assert_eq!(json, r#"{"red":4,"blue":3,"purple":2}"#);
+		assert_eq!(serde_json::from_str::<CubeSet>(&json).unwrap(), set);
+		assert_eq!(
+			serde_json::from_str::<CubeSet>(r#"{"Red":4,"green":0}"#).unwrap(),
+			CubeSet::rgb(4, 0, 0)
+		);
+	}
+}
@@ -0,0 +1,379 @@
+use crate::cubes::CubeSet;
+use crate::game::Game;
+
+// How many cubes the bag may hold in total.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BagSize {
+	AtMost(u32),
+	Exactly(u32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BagEstimate {
+	pub bag: CubeSet,
+	pub log_likelihood: f64,
+}
+
+// Counts up to this many cubes are kept in tables, larger ones are computed when asked
+// for, so a huge bag or limit doesn't allocate a table entry per cube.
+const TABLE_SIZE: u32 = 1 << 16;
+
+// ln(n!) for every n up to the largest bag, or up to TABLE_SIZE, so binomials are
+// mostly sums of table lookups. Beyond the table Stirling's series is exact to well
+// below f64 precision.
+struct LogFactorials(Vec<f64>);
+
+impl LogFactorials {
+	fn new(max: u32) -> LogFactorials {
+		let mut table = vec![0.0; max.min(TABLE_SIZE) as usize + 1];
+		for n in 1..table.len() {
+			table[n] = table[n - 1] + (n as f64).ln();
+		}
+		LogFactorials(table)
+	}
+
+	fn ln_factorial(&self, n: u32) -> f64 {
+		if let Some(&ln) = self.0.get(n as usize) {
+			return ln;
+		}
+		let n = n as f64;
+		n * n.ln() - n + 0.5 * (2.0 * std::f64::consts::PI * n).ln() + 1.0 / (12.0 * n) - 1.0 / (360.0 * n.powi(3))
+	}
+
+	fn ln_binomial(&self, n: u32, k: u32) -> f64 {
+		if k > n {
+			return f64::NEG_INFINITY;
+		}
+		self.ln_factorial(n) - self.ln_factorial(k) - self.ln_factorial(n - k)
+	}
+}
+
+// The log of the chance to see these turns from the bag, when every turn draws its
+// cubes without replacement and puts them back afterwards: a product of multivariate
+// hypergeometric probabilities. Impossible turns give negative infinity.
+pub fn log_likelihood(bag: &CubeSet, turns: &[CubeSet]) -> f64 {
+	let size = bag.total();
+	let largest = turns.iter().map(CubeSet::total).chain([size]).max().unwrap_or(0);
+	let factorials = LogFactorials::new(largest);
+
+	turns
+		.iter()
+		.map(|turn| {
+			let drawn = turn
+				.nonzero()
+				.map(|(color, count)| factorials.ln_binomial(bag.count(color), count))
+				.sum::<f64>();
+			drawn - factorials.ln_binomial(size, turn.total())
+		})
+		.sum()
+}
+
+// Largest `AtMost` limit estimate_bag accepts. Without a fixed total every size up to
+// the limit has to be tried, one cube at a time.
+pub const MAX_SEARCHED_TOTAL: u32 = 1_000_000;
+
+// How much the turns' log-likelihood rises when a bag that could have shown `count`
+// out of `size` cubes gets one more: the sum of ln C(size + 1, count) - ln C(size, count)
+// over the turns. `counts` holds every count a turn showed with how often it did, and
+// the result shrinks as `size` grows, towards 0.
+fn marginal(counts: &[(u32, usize)], size: u32) -> f64 {
+	let next = size as f64 + 1.0;
+	counts
+		.iter()
+		.map(|&(count, turns)| -(-(count as f64) / next).ln_1p() * turns as f64)
+		.sum()
+}
+
+// The counts the turns showed, with how often each was shown. Zeros don't change the
+// likelihood, so they are left out.
+fn tally(counts: impl Iterator<Item = u32>) -> Vec<(u32, usize)> {
+	let mut tally: Vec<(u32, usize)> = Vec::new();
+	for count in counts.filter(|&count| count > 0) {
+		match tally.iter_mut().find(|(seen, _)| *seen == count) {
+			Some((_, turns)) => *turns += 1,
+			None => tally.push((count, 1)),
+		}
+	}
+	tally
+}
+
+// Finds the likeliest bag with at least as many cubes of each seen color as some turn
+// showed, within the size limit. Only colors that were drawn at some point are put in
+// the bag. Ties go to the bag with the fewest cubes, and then to the one with the fewest
+// cubes of the earlier colors. Returns None when no bag of that size fits the turns.
+//
+// Bigger bags make the draws look more independent of each other, which can keep
+// raising the likelihood, so without a fixed total the estimate often ends up at the
+// limit.
+//
+// Panics when an `AtMost` limit is above MAX_SEARCHED_TOTAL.
+pub fn estimate_bag(turns: &[CubeSet], size: BagSize) -> Option<BagEstimate> {
+	let min_world = turns.iter().fold(CubeSet::new(), |min_world, turn| min_world.max(turn));
+	let colors: Vec<(&str, u32)> = min_world.nonzero().collect();
+	let smallest = min_world.total();
+
+	let counts = match size {
+		BagSize::Exactly(total) if total < smallest || (colors.is_empty() && total > 0) => return None,
+		BagSize::Exactly(total) => fill(turns, &colors, total - smallest),
+		BagSize::AtMost(limit) => {
+			assert!(
+				limit <= MAX_SEARCHED_TOTAL,
+				"Can't search bags of up to {} cubes, fix the total or stay at or below {}",
+				limit,
+				MAX_SEARCHED_TOTAL
+			);
+			if limit < smallest {
+				return None;
+			}
+			grow(turns, &colors, limit - smallest)
+		}
+	};
+
+	let bag: CubeSet = colors.iter().zip(counts).map(|(&(color, _), count)| (color, count)).collect();
+	Some(BagEstimate {
+		log_likelihood: log_likelihood(&bag, turns),
+		bag,
+	})
+}
+
+// The log-likelihood splits into a term per color, minus a term for the bag size, and
+// every color's term is concave in its count. So the likeliest bag of each size is the
+// likeliest one a cube smaller plus one cube of whichever color raises the likelihood
+// most. Adds up to `extra` cubes to the smallest bag that fits and keeps the likeliest
+// size on the way; the latest color wins ties, which keeps the earlier ones small.
+fn grow(turns: &[CubeSet], colors: &[(&str, u32)], extra: u32) -> Vec<u32> {
+	let tallies: Vec<Vec<(u32, usize)>> = colors.iter().map(|&(color, _)| tally(turns.iter().map(|turn| turn.count(color)))).collect();
+	let sizes = tally(turns.iter().map(CubeSet::total));
+	let mut counts: Vec<u32> = colors.iter().map(|&(_, count)| count).collect();
+	let mut gains: Vec<f64> = counts.iter().zip(&tallies).map(|(&count, tally)| marginal(tally, count)).collect();
+	let smallest: u32 = counts.iter().sum();
+	let mut best = counts.clone();
+	// relative to the smallest bag
+	let (mut log_likelihood, mut best_log_likelihood) = (0.0, 0.0);
+
+	for size in smallest..smallest + extra {
+		let Some(color) = (0..gains.len()).max_by(|&a, &b| gains[a].total_cmp(&gains[b])) else {
+			break;
+		};
+		log_likelihood += gains[color] - marginal(&sizes, size);
+		counts[color] += 1;
+		gains[color] = marginal(&tallies[color], counts[color]);
+
+		// a little slack so rounding doesn't decide between equally likely bags
+		if log_likelihood > best_log_likelihood + 1e-9 {
+			best_log_likelihood = log_likelihood;
+			best.clone_from(&counts);
+		}
+	}
+	best
+}
+
+// The bag `grow` would reach after `extra` cubes, without adding them one at a time: it
+// holds every cube whose gain is above some threshold, which is found by bisecting over
+// the floats, and the cubes whose gain is exactly the threshold go to the latest colors.
+fn fill(turns: &[CubeSet], colors: &[(&str, u32)], extra: u32) -> Vec<u32> {
+	let tallies: Vec<Vec<(u32, usize)>> = colors.iter().map(|&(color, _)| tally(turns.iter().map(|turn| turn.count(color)))).collect();
+	let minimums: Vec<u32> = colors.iter().map(|&(_, count)| count).collect();
+	if colors.len() < 2 {
+		return minimums.iter().map(|&count| count + extra).collect();
+	}
+
+	// how many cubes of each color gain more than `threshold`, up to `extra` each
+	let above = |threshold: f64| -> Vec<u32> {
+		minimums
+			.iter()
+			.zip(&tallies)
+			.map(|(&minimum, tally)| {
+				let (mut low, mut high) = (0, extra);
+				while low < high {
+					let mid = low + (high - low) / 2;
+					if marginal(tally, minimum + mid) > threshold {
+						low = mid + 1;
+					} else {
+						high = mid;
+					}
+				}
+				low
+			})
+			.collect()
+	};
+	let total = |counts: &[u32]| counts.iter().map(|&count| count as u64).sum::<u64>();
+
+	// gains are positive, so no threshold below 0 is needed, and the floats from 0 up
+	// are ordered like their bits
+	let (mut below, mut at) = (0.0f64.to_bits(), f64::INFINITY.to_bits());
+	while at - below > 1 {
+		let mid = below + (at - below) / 2;
+		if total(&above(f64::from_bits(mid))) <= extra as u64 {
+			at = mid;
+		} else {
+			below = mid;
+		}
+	}
+
+	let mut counts = above(f64::from_bits(at));
+	let mut left = extra - total(&counts) as u32;
+	let tied = above(f64::from_bits(below));
+	for color in (0..counts.len()).rev() {
+		let more = left.min(tied[color] - counts[color]);
+		counts[color] += more;
+		left -= more;
+	}
+	counts.iter().zip(&minimums).map(|(&count, &minimum)| minimum + count).collect()
+}
+
+impl Game {
+	pub fn estimate_bag(&self, size: BagSize) -> Option<BagEstimate> {
+		estimate_bag(&self.turns, size)
+	}
+}
+
+// One bag for a whole log: the likeliest bag to have produced every turn of every game.
+pub fn estimate_common_bag(games: &[Game], size: BagSize) -> Option<BagEstimate> {
+	let turns: Vec<CubeSet> = games.iter().flat_map(|game| game.turns.iter().cloned()).collect();
+	estimate_bag(&turns, size)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn close(a: f64, b: f64) -> bool {
+		(a - b).abs() < 1e-9
+	}
+
+	#[test]
+	fn test_log_likelihood() {
+		// 2 red and 1 blue out of 3 red and 2 blue: C(3,2) * C(2,1) / C(5,3) = 6 / 10
+		let bag = CubeSet::rgb(3, 0, 2);
+		assert!(close(log_likelihood(&bag, &[CubeSet::rgb(2, 0, 1)]), (0.6f64).ln()));
+		assert!(close(log_likelihood(&bag, &[CubeSet::rgb(3, 0, 2)]), 0.0));
+		assert_eq!(log_likelihood(&bag, &[CubeSet::rgb(4, 0, 0)]), f64::NEG_INFINITY);
+		assert_eq!(log_likelihood(&bag, &[CubeSet::rgb(0, 1, 0)]), f64::NEG_INFINITY);
+	}
+
+	#[test]
+	fn test_large_factorials() {
+		let table = LogFactorials::new(1000);
+		let small = LogFactorials::new(10);
+
+		for n in 11..=1000 {
+			assert!((small.ln_factorial(n) - table.ln_factorial(n)).abs() < 1e-6 * table.ln_factorial(n));
+		}
+		assert_eq!(LogFactorials::new(u32::MAX).0.len(), TABLE_SIZE as usize + 1);
+	}
+
+	#[test]
+	fn test_huge_bags() {
+		// one red out of a bag of two billion and one cubes; ln(n!) is around 4e10 here,
+		// so only a few decimals survive the subtraction
+		let bag = CubeSet::rgb(2_000_000_000, 0, 1);
+		let expected = (2_000_000_000f64 / 2_000_000_001f64).ln();
+		assert!((log_likelihood(&bag, &[CubeSet::rgb(1, 0, 0)]) - expected).abs() < 1e-3);
+
+		let game = Game::from_game_line("Game 1: 1 red").unwrap();
+		assert_eq!(
+			game.estimate_bag(BagSize::Exactly(3_000_000_000)).unwrap().bag,
+			CubeSet::rgb(3_000_000_000, 0, 0)
+		);
+	}
+
+	#[test]
+	fn test_tight_bag_for_full_draws() {
+		// every turn emptied the bag, so it can't have held anything else
+		let game = Game::from_game_line("Game 1: 3 red, 2 blue; 2 blue, 3 red").unwrap();
+		let estimate = game.estimate_bag(BagSize::AtMost(20)).unwrap();
+
+		assert_eq!(estimate.bag, CubeSet::rgb(3, 0, 2));
+		assert!(close(estimate.log_likelihood, 0.0));
+	}
+
+	#[test]
+	fn test_estimate_follows_proportions() {
+		// one cube per turn: red came up three times as often as blue
+		let game = Game::from_game_line("Game 1: 1 red; 1 red; 1 blue; 1 red").unwrap();
+		let estimate = game.estimate_bag(BagSize::Exactly(8)).unwrap();
+
+		assert_eq!(estimate.bag, CubeSet::rgb(6, 0, 2));
+	}
+
+	#[test]
+	fn test_fixed_total() {
+		let game = Game::from_game_line("Game 1: 4 red, 1 green; 2 blue").unwrap();
+
+		assert_eq!(game.estimate_bag(BagSize::Exactly(6)), None);
+		assert_eq!(game.estimate_bag(BagSize::Exactly(7)).unwrap().bag, CubeSet::rgb(4, 1, 2));
+		assert_eq!(game.estimate_bag(BagSize::AtMost(6)), None);
+		assert_eq!(game.estimate_bag(BagSize::Exactly(12)).unwrap().bag.total(), 12);
+	}
+
+	#[test]
+	fn test_ties_keep_earlier_colors_small() {
+		let game = Game::from_game_line("Game 1: 1 red, 1 blue").unwrap();
+
+		assert_eq!(game.estimate_bag(BagSize::Exactly(5)).unwrap().bag, CubeSet::rgb(2, 0, 3));
+	}
+
+	#[test]
+	fn test_best_bag_of_each_size() {
+		let game = Game::from_game_line("Game 1: 2 red, 1 green; 1 red, 1 blue; 3 red; 1 green, 2 blue").unwrap();
+
+		for total in 6..=15 {
+			let estimate = game.estimate_bag(BagSize::Exactly(total)).unwrap();
+			assert_eq!(estimate.bag.total(), total);
+			for red in 3..=total {
+				for green in 1..=total - red {
+					let bag = CubeSet::rgb(red, green, total - red - green);
+					assert!(log_likelihood(&bag, &game.turns) <= estimate.log_likelihood + 1e-9);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_large_totals() {
+		let game = Game::from_game_line("Game 1: 3 red, 2 green, 1 blue").unwrap();
+
+		let estimate = game.estimate_bag(BagSize::Exactly(200_000)).unwrap();
+		assert_eq!(estimate.bag.total(), 200_000);
+		assert!(estimate.bag.count("red") > estimate.bag.count("green"));
+		assert!(estimate.bag.count("green") > estimate.bag.count("blue"));
+		assert_eq!(game.estimate_bag(BagSize::Exactly(u32::MAX)).unwrap().bag.total(), u32::MAX);
+		assert!(game.estimate_bag(BagSize::AtMost(MAX_SEARCHED_TOTAL)).is_some());
+	}
+
+	#[test]
+	#[should_panic(expected = "Can't search bags")]
+	fn test_search_limit() {
+		let game = Game::from_game_line("Game 1: 1 red").unwrap();
+		game.estimate_bag(BagSize::AtMost(MAX_SEARCHED_TOTAL + 1));
+	}
+
+	#[test]
+	fn test_best_of_all_bags() {
+		let game = Game::from_game_line("Game 1: 2 red, 1 green; 1 red, 1 blue; 3 red").unwrap();
+		let estimate = game.estimate_bag(BagSize::AtMost(12)).unwrap();
+
+		for red in 3..=10 {
+			for green in 1..=(11 - red) {
+				for blue in 1..=(12 - red - green) {
+					let bag = CubeSet::rgb(red, green, blue);
+					assert!(log_likelihood(&bag, &game.turns) <= estimate.log_likelihood + 1e-9);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_common_bag() {
+		let games = vec![
+			Game::from_game_line("Game 1: 1 red; 1 red").unwrap(),
+			Game::from_game_line("Game 2: 1 blue; 1 red").unwrap(),
+		];
+		let estimate = estimate_common_bag(&games, BagSize::Exactly(4)).unwrap();
+
+		assert_eq!(estimate.bag, CubeSet::rgb(3, 0, 1));
+		assert_eq!(estimate_common_bag(&[], BagSize::AtMost(5)).unwrap().bag, CubeSet::new());
+		assert_eq!(estimate_common_bag(&[], BagSize::Exactly(5)), None);
+	}
+}
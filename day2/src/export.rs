@@ -0,0 +1,262 @@
+use std::fmt;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::cubes::{self, CubeSet, RGB};
+use crate::game::{self, Game, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+	// one `Game N: ...` line per game
+	Text,
+	// a list of games, each with its turns as maps from color to count
+	Json,
+	// one row per turn
+	Csv,
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+	Text(ParseError),
+	Json(serde_json::Error),
+	Csv(csv::Error),
+	Record { record: usize, reason: String },
+}
+
+impl fmt::Display for ExportError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ExportError::Text(err) => write!(f, "{}", err),
+			ExportError::Json(err) => write!(f, "invalid JSON: {}", err),
+			ExportError::Csv(err) => write!(f, "invalid CSV: {}", err),
+			ExportError::Record { record, reason } => write!(f, "record {}: {}", record, reason),
+		}
+	}
+}
+
+impl std::error::Error for ExportError {}
+
+// A CSV row: one turn of a game, with red, green and blue in their own columns and
+// any other colors written like in a game log ("2 purple, 1 yellow"). A game without
+// turns gets a single row with no turn number.
+#[derive(Debug, Serialize, Deserialize)]
+struct TurnRecord {
+	game: u32,
+	turn: Option<usize>,
+	red: u32,
+	green: u32,
+	blue: u32,
+	other: String,
+}
+
+impl TurnRecord {
+	fn new(game: u32, turn: Option<(usize, &CubeSet)>) -> TurnRecord {
+		let (number, cubes) = match turn {
+			Some((number, cubes)) => (Some(number), cubes.clone()),
+			None => (None, CubeSet::new()),
+		};
+		let other: Vec<String> = cubes
+			.nonzero()
+			.filter(|(color, _)| !RGB.contains(color))
+			.map(|(color, count)| format!("{} {}", count, color))
+			.collect();
+
+		TurnRecord {
+			game,
+			turn: number,
+			red: cubes.count("red"),
+			green: cubes.count("green"),
+			blue: cubes.count("blue"),
+			other: other.join(", "),
+		}
+	}
+
+	fn cubes(&self) -> Result<CubeSet, String> {
+		let mut cubes: CubeSet = self.other.parse().map_err(|err| format!("{}", err))?;
+		if let Some(color) = RGB.iter().find(|color| cubes.count(color) > 0) {
+			return Err(format!("{} belongs in its own column", color));
+		}
+		cubes.set("red", self.red);
+		cubes.set("green", self.green);
+		cubes.set("blue", self.blue);
+		Ok(cubes)
+	}
+}
+
+pub fn to_text(games: &[Game]) -> String {
+	games.iter().map(|game| format!("{}\n", game)).collect()
+}
+
+pub fn from_text(text: &str) -> Result<Vec<Game>, ExportError> {
+	game::parse_game_file(text.lines()).map_err(ExportError::Text)
+}
+
+pub fn to_json(games: &[Game]) -> Result<String, ExportError> {
+	serde_json::to_string_pretty(games).map_err(ExportError::Json)
+}
+
+// Colors are checked like the text parser checks them, so every log that is read can
+// be written back in any format. Records count the games of the list.
+pub fn from_json(text: &str) -> Result<Vec<Game>, ExportError> {
+	let games: Vec<Game> = serde_json::from_str(text).map_err(ExportError::Json)?;
+
+	for (index, game) in games.iter().enumerate() {
+		let mut colors = game.turns.iter().flat_map(|turn| turn.nonzero());
+		if let Some((color, _)) = colors.find(|(color, _)| !cubes::is_color_name(color)) {
+			return Err(ExportError::Record {
+				record: index + 1,
+				reason: format!("{:?} is not a color name", color),
+			});
+		}
+	}
+
+	Ok(games)
+}
+
+pub fn to_csv(games: &[Game]) -> Result<String, ExportError> {
+	let mut writer = csv::Writer::from_writer(Vec::new());
+
+	for game in games {
+		if game.turns.is_empty() {
+			writer.serialize(TurnRecord::new(game.id, None)).map_err(ExportError::Csv)?;
+		}
+		for (index, turn) in game.turns.iter().enumerate() {
+			writer
+				.serialize(TurnRecord::new(game.id, Some((index + 1, turn))))
+				.map_err(ExportError::Csv)?;
+		}
+	}
+
+	let bytes = writer.into_inner().map_err(|err| ExportError::Csv(err.into_error().into()))?;
+	Ok(String::from_utf8(bytes).expect("CSV output is always UTF-8"))
+}
+
+// Rows are read in order: turn 1 (or a row without a turn) starts a new game, and
+// every other turn has to continue the game of the row before it.
+pub fn from_csv(text: &str) -> Result<Vec<Game>, ExportError> {
+	let mut reader = csv::Reader::from_reader(text.as_bytes());
+	let mut games: Vec<Game> = Vec::new();
+
+	for (index, record) in reader.deserialize().enumerate() {
+		let record: TurnRecord = record.map_err(ExportError::Csv)?;
+		let invalid = |reason: String| ExportError::Record {
+			record: index + 1,
+			reason,
+		};
+
+		match record.turn {
+			None | Some(1) => games.push(Game::new(record.game)),
+			Some(turn) => match games.last() {
+				Some(game) if game.id == record.game && game.turns.len() + 1 == turn => {}
+				_ => return Err(invalid(format!("turn {} of game {} doesn't follow the row before it", turn, record.game))),
+			},
+		}
+		if record.turn.is_some() {
+			let cubes = record.cubes().map_err(invalid)?;
+			games.last_mut().unwrap().add_turn(cubes);
+		}
+	}
+
+	Ok(games)
+}
+
+pub fn write(games: &[Game], format: Format) -> Result<String, ExportError> {
+	match format {
+		Format::Text => Ok(to_text(games)),
+		Format::Json => to_json(games),
+		Format::Csv => to_csv(games),
+	}
+}
+
+pub fn read(text: &str, format: Format) -> Result<Vec<Game>, ExportError> {
+	match format {
+		Format::Text => from_text(text),
+		Format::Json => from_json(text),
+		Format::Csv => from_csv(text),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const LOG: &str = "Game 1: 4 red, 3 blue; 1 red, 2 green, 6 blue; 2 green
+Game 2:
+Game 3: 0 red, 0 green, 0 blue; 1 red, 2 purple, 1 yellow
+Game 1: 7 green
+";
+
+	#[test]
+	fn test_round_trips() {
+		let games = from_text(LOG).unwrap();
+
+		assert_eq!(to_text(&games), LOG);
+		for format in [Format::Text, Format::Json, Format::Csv] {
+			let written = write(&games, format).unwrap();
+			assert_eq!(read(&written, format).unwrap(), games, "{:?}", format);
+		}
+	}
+
+	#[test]
+	fn test_csv_layout() {
+		let games = from_text(LOG).unwrap();
+
+		assert_eq!(
+			to_csv(&games).unwrap(),
+			"game,turn,red,green,blue,other
+1,1,4,0,3,
+1,2,1,2,6,
+1,3,0,2,0,
+2,,0,0,0,
+3,1,0,0,0,
+3,2,1,0,0,\"2 purple, 1 yellow\"
+1,1,0,7,0,
+"
+		);
+	}
+
+	#[test]
+	fn test_json_layout() {
+		let games = from_text("Game 2: 4 red, 3 blue; 1 purple").unwrap();
+
+		assert_eq!(
+			serde_json::to_string(&games).unwrap(),
+			r#"[{"id":2,"turns":[{"red":4,"blue":3},{"purple":1}]}]"#
+		);
+	}
+
+	#[test]
+	fn test_invalid_records() {
+		let skipped = "game,turn,red,green,blue,other\n1,1,1,0,0,\n1,3,1,0,0,\n";
+		let misplaced = "game,turn,red,green,blue,other\n1,1,1,0,0,2 red\n";
+
+		assert_eq!(
+			from_csv(skipped).unwrap_err().to_string(),
+			"record 2: turn 3 of game 1 doesn't follow the row before it"
+		);
+		assert_eq!(
+			from_csv(misplaced).unwrap_err().to_string(),
+			"record 1: red belongs in its own column"
+		);
+		assert!(matches!(from_json("[{\"id\": 1}]"), Err(ExportError::Json(_))));
+	}
+
+	#[test]
+	fn test_invalid_json_colors() {
+		for color in ["", "dark red", "red,", "red;blue", "r3d"] {
+			let json = format!(r#"[{{"id":1,"turns":[{{"red":1}}]}},{{"id":2,"turns":[{{"{}":1}}]}}]"#, color);
+
+			assert_eq!(
+				from_json(&json).unwrap_err().to_string(),
+				format!("record 2: {:?} is not a color name", color)
+			);
+		}
+
+		// a color without cubes is dropped anyway
+		assert_eq!(
+			from_json(r#"[{"id":1,"turns":[{"":0,"Grün":2}]}]"#).unwrap(),
+			from_text("Game 1: 2 grün").unwrap()
+		);
+	}
+}
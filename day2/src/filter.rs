@@ -0,0 +1,456 @@
+use std::fmt;
+
+use crate::cubes::CubeSet;
+use crate::game::Game;
+
+// A condition on a game, like `max(red) > 12 and turns >= 3 or any(blue == 0)`.
+//
+//   expr    = and ("or" and)*
+//   and     = not ("and" not)*
+//   not     = "not" not | "(" expr ")" | "any(" expr ")" | "all(" expr ")" | compare
+//   compare = value ("==" | "!=" | "<" | "<=" | ">" | ">=") value
+//   value   = number | "id" | "turns" | "power" | max(color) | min(color) | sum(color)
+//           | color | "total"
+//
+// `any` and `all` check their condition against every turn of the game. Inside them a
+// bare color is the number of cubes of that color shown in the turn, and `total` the
+// number of cubes in the turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+	Or(Box<Filter>, Box<Filter>),
+	And(Box<Filter>, Box<Filter>),
+	Not(Box<Filter>),
+	Any(Box<Filter>),
+	All(Box<Filter>),
+	Compare(Value, Comparison, Value),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+	Number(u64),
+	Id,
+	Turns,
+	Power,
+	Max(String),
+	Min(String),
+	Sum(String),
+	// only inside any(...) and all(...)
+	Count(String),
+	Total,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+	Equal,
+	NotEqual,
+	Less,
+	LessOrEqual,
+	Greater,
+	GreaterOrEqual,
+}
+
+// `position` is a byte offset into the expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterError {
+	pub position: usize,
+	pub message: String,
+}
+
+impl fmt::Display for FilterError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} at column {}", self.message, self.position + 1)
+	}
+}
+
+impl std::error::Error for FilterError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+	Word(String),
+	Number(u64),
+	Comparison(Comparison),
+	Open,
+	Close,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<(usize, Token)>, FilterError> {
+	let mut tokens = Vec::new();
+	let mut chars = expression.char_indices().peekable();
+
+	while let Some(&(start, c)) = chars.peek() {
+		let error = |message: String| FilterError {
+			position: start,
+			message,
+		};
+
+		if c.is_whitespace() {
+			chars.next();
+		} else if c.is_alphabetic() || c == '_' {
+			let mut word = String::new();
+			while let Some(&(_, c)) = chars.peek().filter(|(_, c)| c.is_alphanumeric() || *c == '_') {
+				word.push(c);
+				chars.next();
+			}
+			tokens.push((start, Token::Word(word.to_lowercase())));
+		} else if c.is_ascii_digit() {
+			let mut digits = String::new();
+			while let Some(&(_, c)) = chars.peek().filter(|(_, c)| c.is_ascii_digit()) {
+				digits.push(c);
+				chars.next();
+			}
+			let number = digits.parse().map_err(|_| error(format!("number {} is too large", digits)))?;
+			tokens.push((start, Token::Number(number)));
+		} else if c == '(' || c == ')' {
+			chars.next();
+			tokens.push((start, if c == '(' { Token::Open } else { Token::Close }));
+		} else {
+			chars.next();
+			let two = chars.peek().map(|&(_, next)| next) == Some('=');
+			let comparison = match (c, two) {
+				('=', true) => Comparison::Equal,
+				('!', true) => Comparison::NotEqual,
+				('<', true) => Comparison::LessOrEqual,
+				('>', true) => Comparison::GreaterOrEqual,
+				('<', false) => Comparison::Less,
+				('>', false) => Comparison::Greater,
+				_ => return Err(error(format!("unexpected '{}'", c))),
+			};
+			if two {
+				chars.next();
+			}
+			tokens.push((start, Token::Comparison(comparison)));
+		}
+	}
+
+	Ok(tokens)
+}
+
+// How deep `not`, parentheses, any(...) and all(...) may nest. The parser recurses
+// once per level, so without a limit a long `not not ...` overflows the stack.
+const MAX_DEPTH: usize = 256;
+
+struct Parser {
+	tokens: Vec<(usize, Token)>,
+	index: usize,
+	end: usize,
+	// how many any(...) and all(...) we are inside of
+	turn_depth: usize,
+	// how many of any kind of nesting we are inside of
+	depth: usize,
+}
+
+impl Parser {
+	fn peek(&self) -> Option<&Token> {
+		self.tokens.get(self.index).map(|(_, token)| token)
+	}
+
+	fn position(&self) -> usize {
+		self.tokens.get(self.index).map_or(self.end, |&(position, _)| position)
+	}
+
+	fn error<T>(&self, message: &str) -> Result<T, FilterError> {
+		let found = match self.peek() {
+			Some(Token::Word(word)) => format!("'{}'", word),
+			Some(Token::Number(number)) => number.to_string(),
+			Some(Token::Comparison(_)) => "a comparison".to_string(),
+			Some(Token::Open) => "'('".to_string(),
+			Some(Token::Close) => "')'".to_string(),
+			None => "the end".to_string(),
+		};
+		Err(FilterError {
+			position: self.position(),
+			message: format!("expected {}, found {}", message, found),
+		})
+	}
+
+	fn next_is_word(&self, word: &str) -> bool {
+		matches!(self.peek(), Some(Token::Word(next)) if next == word)
+	}
+
+	fn expect(&mut self, token: Token, message: &str) -> Result<(), FilterError> {
+		if self.peek() != Some(&token) {
+			return self.error(message);
+		}
+		self.index += 1;
+		Ok(())
+	}
+
+	// Parses one level deeper, or fails when that goes past MAX_DEPTH.
+	fn nested<F>(&mut self, parse: F) -> Result<Filter, FilterError>
+	where
+		F: FnOnce(&mut Parser) -> Result<Filter, FilterError>,
+	{
+		if self.depth == MAX_DEPTH {
+			return Err(FilterError {
+				position: self.position(),
+				message: format!("conditions can't be nested more than {} deep", MAX_DEPTH),
+			});
+		}
+		self.depth += 1;
+		let filter = parse(self);
+		self.depth -= 1;
+		filter
+	}
+
+	fn or(&mut self) -> Result<Filter, FilterError> {
+		let mut filter = self.and()?;
+		while self.next_is_word("or") {
+			self.index += 1;
+			filter = Filter::Or(Box::new(filter), Box::new(self.and()?));
+		}
+		Ok(filter)
+	}
+
+	fn and(&mut self) -> Result<Filter, FilterError> {
+		let mut filter = self.not()?;
+		while self.next_is_word("and") {
+			self.index += 1;
+			filter = Filter::And(Box::new(filter), Box::new(self.not()?));
+		}
+		Ok(filter)
+	}
+
+	fn not(&mut self) -> Result<Filter, FilterError> {
+		if self.next_is_word("not") {
+			return self.nested(|parser| {
+				parser.index += 1;
+				Ok(Filter::Not(Box::new(parser.not()?)))
+			});
+		}
+		if self.peek() == Some(&Token::Open) {
+			return self.nested(|parser| {
+				parser.index += 1;
+				let filter = parser.or()?;
+				parser.expect(Token::Close, "')'")?;
+				Ok(filter)
+			});
+		}
+		for (word, quantifier) in [("any", Filter::Any as fn(_) -> _), ("all", Filter::All)] {
+			if self.next_is_word(word) {
+				return self.nested(|parser| {
+					parser.index += 1;
+					parser.expect(Token::Open, "'(' after any or all")?;
+					parser.turn_depth += 1;
+					let filter = parser.or()?;
+					parser.turn_depth -= 1;
+					parser.expect(Token::Close, "')'")?;
+					Ok(quantifier(Box::new(filter)))
+				});
+			}
+		}
+		self.compare()
+	}
+
+	fn compare(&mut self) -> Result<Filter, FilterError> {
+		let left = self.value()?;
+		let comparison = match self.peek() {
+			Some(Token::Comparison(comparison)) => *comparison,
+			_ => return self.error("a comparison"),
+		};
+		self.index += 1;
+		let right = self.value()?;
+		Ok(Filter::Compare(left, comparison, right))
+	}
+
+	fn color_argument(&mut self) -> Result<String, FilterError> {
+		self.expect(Token::Open, "'('")?;
+		let color = match self.peek() {
+			Some(Token::Word(color)) => color.clone(),
+			_ => return self.error("a color"),
+		};
+		self.index += 1;
+		self.expect(Token::Close, "')'")?;
+		Ok(color)
+	}
+
+	fn value(&mut self) -> Result<Value, FilterError> {
+		let position = self.position();
+		let word = match self.peek() {
+			Some(Token::Number(number)) => {
+				let number = *number;
+				self.index += 1;
+				return Ok(Value::Number(number));
+			}
+			Some(Token::Word(word)) => word.clone(),
+			_ => return self.error("a value"),
+		};
+		if ["and", "or", "not", "any", "all"].contains(&word.as_str()) {
+			return self.error("a value");
+		}
+		self.index += 1;
+
+		let value = match word.as_str() {
+			"id" => Value::Id,
+			"turns" => Value::Turns,
+			"power" => Value::Power,
+			"max" => Value::Max(self.color_argument()?),
+			"min" => Value::Min(self.color_argument()?),
+			"sum" => Value::Sum(self.color_argument()?),
+			"total" => Value::Total,
+			_ => Value::Count(word.clone()),
+		};
+		if matches!(value, Value::Total | Value::Count(_)) && self.turn_depth == 0 {
+			return Err(FilterError {
+				position,
+				message: format!("'{}' is only known inside any(...) or all(...)", word),
+			});
+		}
+		Ok(value)
+	}
+}
+
+impl Filter {
+	pub fn parse(expression: &str) -> Result<Filter, FilterError> {
+		let mut parser = Parser {
+			tokens: tokenize(expression)?,
+			index: 0,
+			end: expression.len(),
+			turn_depth: 0,
+			depth: 0,
+		};
+		let filter = parser.or()?;
+		if parser.peek().is_some() {
+			return parser.error("'and', 'or' or the end");
+		}
+		Ok(filter)
+	}
+
+	pub fn matches(&self, game: &Game) -> bool {
+		self.evaluate(game, None)
+	}
+
+	fn evaluate(&self, game: &Game, turn: Option<&CubeSet>) -> bool {
+		match self {
+			Filter::Or(left, right) => left.evaluate(game, turn) || right.evaluate(game, turn),
+			Filter::And(left, right) => left.evaluate(game, turn) && right.evaluate(game, turn),
+			Filter::Not(filter) => !filter.evaluate(game, turn),
+			Filter::Any(filter) => game.turns.iter().any(|turn| filter.evaluate(game, Some(turn))),
+			Filter::All(filter) => game.turns.iter().all(|turn| filter.evaluate(game, Some(turn))),
+			Filter::Compare(left, comparison, right) => {
+				let (left, right) = (left.evaluate(game, turn), right.evaluate(game, turn));
+				match comparison {
+					Comparison::Equal => left == right,
+					Comparison::NotEqual => left != right,
+					Comparison::Less => left < right,
+					Comparison::LessOrEqual => left <= right,
+					Comparison::Greater => left > right,
+					Comparison::GreaterOrEqual => left >= right,
+				}
+			}
+		}
+	}
+}
+
+impl Value {
+	fn evaluate(&self, game: &Game, turn: Option<&CubeSet>) -> u64 {
+		let counts = |color: &String| {
+			let counts: Vec<u64> = game.turns.iter().map(|turn| turn.count(color) as u64).collect();
+			counts.into_iter()
+		};
+		match self {
+			Value::Number(number) => *number,
+			Value::Id => game.id as u64,
+			Value::Turns => game.turns.len() as u64,
+			// a power too large for a u64 is still larger than any number in a filter
+			Value::Power => game.calc_min_world().product().unwrap_or(u64::MAX),
+			Value::Max(color) => counts(color).max().unwrap_or(0),
+			Value::Min(color) => counts(color).min().unwrap_or(0),
+			Value::Sum(color) => counts(color).sum(),
+			// parsing only allows these inside any(...) and all(...)
+			Value::Count(color) => turn.map_or(0, |turn| turn.count(color) as u64),
+			Value::Total => turn.map_or(0, |turn| turn.total() as u64),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::game::parse_game_file;
+
+	const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+	fn matching(expression: &str) -> Vec<u32> {
+		let filter = Filter::parse(expression).unwrap();
+		parse_game_file(EXAMPLE.lines())
+			.unwrap()
+			.iter()
+			.filter(|game| filter.matches(game))
+			.map(|game| game.id)
+			.collect()
+	}
+
+	#[test]
+	fn test_game_values() {
+		assert_eq!(matching("max(red) <= 12 and max(green) <= 13 and max(blue) <= 14"), vec![1, 2, 5]);
+		assert_eq!(matching("turns == 2"), vec![5]);
+		assert_eq!(matching("power > 100"), vec![3, 4]);
+		assert_eq!(matching("min(red) >= 1"), vec![3, 4, 5]);
+		assert_eq!(matching("sum(green) == 4"), vec![1]);
+		assert_eq!(matching("id != 3 and 4 > id"), vec![1, 2]);
+	}
+
+	#[test]
+	fn test_power_of_other_colors() {
+		let filter = Filter::parse("power == 10").unwrap();
+		let game = Game::from_game_line("Game 1: 2 purple; 5 teal, 1 purple").unwrap();
+
+		assert!(filter.matches(&game));
+	}
+
+	#[test]
+	fn test_turn_conditions() {
+		assert_eq!(matching("any(blue == 0)"), vec![1, 3, 4]);
+		assert_eq!(matching("all(total <= 10)"), vec![1, 2, 5]);
+		assert_eq!(matching("any(red > green and blue > 0)"), vec![1, 3, 4, 5]);
+		assert_eq!(matching("any(purple == 0)"), vec![1, 2, 3, 4, 5]);
+	}
+
+	#[test]
+	fn test_precedence() {
+		// (max(red) > 12 and turns >= 3) or any(blue == 0)
+		assert_eq!(matching("max(red) > 12 and turns >= 3 or any(blue == 0)"), vec![1, 3, 4]);
+		assert_eq!(matching("max(red) > 12 and (turns >= 3 or any(blue == 0))"), vec![3, 4]);
+		assert_eq!(matching("not id == 1 and not (id == 2 or id == 3)"), vec![4, 5]);
+		assert_eq!(
+			Filter::parse("id == 1 or id == 2 and turns > 2").unwrap(),
+			Filter::parse("id == 1 or (id == 2 and turns > 2)").unwrap()
+		);
+	}
+
+	#[test]
+	fn test_errors() {
+		let error = |expression| Filter::parse(expression).unwrap_err().to_string();
+
+		assert_eq!(error("max(red) >"), "expected a value, found the end at column 11");
+		assert_eq!(error("red > 3"), "'red' is only known inside any(...) or all(...) at column 1");
+		assert_eq!(error("id = 3"), "unexpected '=' at column 4");
+		assert_eq!(error("any(blue == 0"), "expected ')', found the end at column 14");
+		assert_eq!(error("turns > 2 turns"), "expected 'and', 'or' or the end, found 'turns' at column 11");
+		assert_eq!(error("max(3) > 1"), "expected a color, found 3 at column 5");
+		assert_eq!(error("turns"), "expected a comparison, found the end at column 6");
+	}
+
+	#[test]
+	fn test_nesting_limit() {
+		let nots = |count| format!("{}id == 1", "not ".repeat(count));
+		let parens = |count| format!("{}id == 1{}", "(".repeat(count), ")".repeat(count));
+
+		assert!(Filter::parse(&nots(MAX_DEPTH)).is_ok());
+		assert!(Filter::parse(&parens(MAX_DEPTH)).is_ok());
+		assert_eq!(
+			Filter::parse(&nots(MAX_DEPTH + 1)).unwrap_err(),
+			FilterError {
+				position: 4 * MAX_DEPTH,
+				message: "conditions can't be nested more than 256 deep".to_string(),
+			}
+		);
+		assert!(Filter::parse(&parens(100_000)).is_err());
+		assert!(Filter::parse(&nots(100_000)).is_err());
+		assert!(Filter::parse(&"any(".repeat(100_000)).is_err());
+	}
+}
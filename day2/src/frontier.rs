@@ -0,0 +1,182 @@
+use crate::cubes::CubeSet;
+use crate::game::Game;
+
+// A bag on the frontier: no bag with fewer cubes makes as many games possible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontierPoint {
+	pub bag: CubeSet,
+	pub size: u32,
+	pub possible: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frontier {
+	pub games: usize,
+	// by increasing size, and so by increasing number of possible games
+	pub points: Vec<FrontierPoint>,
+}
+
+// The Pareto frontier of bags by size and number of possible games. Of equally good
+// bags of one size, the one with the fewest cubes of the earlier colors is kept. A game is possible
+// with a bag exactly when its minimal bag fits in it, so only counts that some game
+// needs are worth trying for each color; anything in between adds cubes without making
+// another game possible. That keeps the search to the product of the distinct counts
+// per color, which is small for real logs but grows quickly with the number of colors.
+pub fn frontier(games: &[Game]) -> Frontier {
+	let min_worlds: Vec<CubeSet> = games.iter().map(Game::calc_min_world).collect();
+	let needed = min_worlds.iter().fold(CubeSet::new(), |needed, world| needed.max(world));
+	let candidates: Vec<(&str, Vec<u32>)> = needed
+		.nonzero()
+		.map(|(color, _)| {
+			let mut counts: Vec<u32> = min_worlds.iter().map(|world| world.count(color)).chain([0]).collect();
+			counts.sort_unstable();
+			counts.dedup();
+			(color, counts)
+		})
+		.collect();
+
+	let mut bags = Vec::new();
+	let mut bag = CubeSet::new();
+	enumerate(&candidates, &mut bag, &mut |bag| {
+		let possible = min_worlds.iter().filter(|world| world.is_subset_of(bag)).count();
+		bags.push(FrontierPoint {
+			bag: bag.clone(),
+			size: bag.total(),
+			possible,
+		});
+	});
+
+	// smallest bags first, and of those the one making the most games possible
+	bags.sort_by_key(|point| (point.size, std::cmp::Reverse(point.possible)));
+	let mut points: Vec<FrontierPoint> = Vec::new();
+	for point in bags {
+		let better = match points.last() {
+			Some(last) => point.possible > last.possible,
+			None => true,
+		};
+		if better {
+			points.push(point);
+		}
+	}
+
+	Frontier {
+		games: games.len(),
+		points,
+	}
+}
+
+fn enumerate<F: FnMut(&CubeSet)>(candidates: &[(&str, Vec<u32>)], bag: &mut CubeSet, visit: &mut F) {
+	match candidates.split_first() {
+		Some(((color, counts), rest)) => {
+			for &count in counts {
+				bag.set(color, count);
+				enumerate(rest, bag, visit);
+			}
+			bag.set(color, 0);
+		}
+		None => visit(bag),
+	}
+}
+
+impl Frontier {
+	pub fn fraction(&self, point: &FrontierPoint) -> f64 {
+		if self.games == 0 {
+			return 1.0;
+		}
+		point.possible as f64 / self.games as f64
+	}
+
+	// The smallest bag making at least this many games possible.
+	pub fn smallest_for(&self, possible: usize) -> Option<&FrontierPoint> {
+		self.points.iter().find(|point| point.possible >= possible)
+	}
+
+	// The bag making the most games possible with at most this many cubes.
+	pub fn best_within(&self, max_size: u32) -> Option<&FrontierPoint> {
+		self.points.iter().rev().find(|point| point.size <= max_size)
+	}
+
+	// Frontier bags with at most `max_size` cubes that make at least `min_fraction` of
+	// the games possible, e.g. `query(40, 0.9)`. Empty when no such bag exists.
+	pub fn query(&self, max_size: u32, min_fraction: f64) -> Vec<&FrontierPoint> {
+		self.points
+			.iter()
+			.filter(|point| point.size <= max_size && self.fraction(point) >= min_fraction)
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::game::parse_game_file;
+
+	const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+	fn sizes_and_counts(frontier: &Frontier) -> Vec<(u32, usize)> {
+		frontier.points.iter().map(|point| (point.size, point.possible)).collect()
+	}
+
+	#[test]
+	fn test_frontier() {
+		let games = parse_game_file(EXAMPLE.lines()).unwrap();
+		let frontier = frontier(&games);
+
+		assert_eq!(frontier.games, 5);
+		assert_eq!(sizes_and_counts(&frontier), vec![(0, 0), (8, 1), (13, 2), (15, 3), (32, 4), (48, 5)]);
+		// ties with the 6 red, 3 green, 4 blue of games 2 and 5; fewer red cubes come first
+		assert_eq!(frontier.points[2].bag, CubeSet::rgb(4, 3, 6));
+		for point in &frontier.points {
+			let possible = games.iter().filter(|game| game.is_possible(&point.bag)).count();
+			assert_eq!(possible, point.possible);
+		}
+	}
+
+	#[test]
+	fn test_no_smaller_bag_does_better() {
+		let games = parse_game_file(EXAMPLE.lines()).unwrap();
+		let frontier = frontier(&games);
+
+		for red in 0..=20 {
+			for green in 0..=13 {
+				for blue in 0..=15 {
+					let bag = CubeSet::rgb(red, green, blue);
+					let possible = games.iter().filter(|game| game.is_possible(&bag)).count();
+					let best = frontier.best_within(bag.total()).unwrap();
+					assert!(possible <= best.possible, "{:?}", bag);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_queries() {
+		let games = parse_game_file(EXAMPLE.lines()).unwrap();
+		let frontier = frontier(&games);
+
+		assert_eq!(frontier.smallest_for(3).unwrap().size, 15);
+		assert_eq!(frontier.smallest_for(6), None);
+		assert_eq!(frontier.best_within(40).unwrap().possible, 4);
+		assert_eq!(
+			frontier.query(40, 0.6).iter().map(|point| point.size).collect::<Vec<_>>(),
+			vec![15, 32]
+		);
+		assert!(frontier.query(40, 0.9).is_empty());
+	}
+
+	#[test]
+	fn test_other_colors() {
+		let games = parse_game_file(["Game 1: 2 purple", "Game 2: 1 purple, 1 yellow"]).unwrap();
+		let frontier = frontier(&games);
+
+		assert_eq!(sizes_and_counts(&frontier), vec![(0, 0), (2, 1), (3, 2)]);
+		assert_eq!(
+			frontier.points[2].bag,
+			[("purple", 2), ("yellow", 1)].into_iter().collect()
+		);
+	}
+}
@@ -1,11 +1,86 @@
+use std::fmt;
+use std::str::FromStr;
+
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
-type GameTurn = (i32, i32, i32);
+use crate::cubes::CubeSet;
+use crate::validation::{Diagnostic, DiagnosticKind, DuplicatePolicy};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Game {
 	pub id: u32,
-	pub turns: Vec<GameTurn>,
+	pub turns: Vec<CubeSet>,
+}
+
+// What is wrong with a single game line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineError {
+	MissingHeader,
+	InvalidId(String),
+	InvalidCubes(String),
+	NegativeCount(String),
+	CountTooLarge { cubes: String, max: u32 },
+	DuplicateColor { turn: String, color: String },
+}
+
+impl fmt::Display for LineError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			LineError::MissingHeader => write!(f, "expected a line like 'Game 1: 3 blue, 4 red'"),
+			LineError::InvalidId(id) => write!(f, "invalid game id '{}'", id),
+			LineError::InvalidCubes(cubes) => {
+				write!(f, "invalid cubes '{}', expected a count and a color like '3 blue'", cubes)
+			}
+			LineError::NegativeCount(cubes) => write!(f, "negative cube count in '{}'", cubes),
+			LineError::CountTooLarge { cubes, max } => {
+				write!(f, "too many cubes in '{}', at most {} are allowed", cubes, max)
+			}
+			LineError::DuplicateColor { turn, color } => {
+				write!(f, "'{}' counts {} more than once", turn, color)
+			}
+		}
+	}
+}
+
+impl std::error::Error for LineError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+	pub line_number: usize,
+	pub line: String,
+	pub error: LineError,
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "line {}: {} in {:?}", self.line_number, self.error, self.line)
+	}
+}
+
+impl std::error::Error for ParseError {}
+
+// The games of a log parsed leniently: every line that parsed, what was wrong with
+// the others, and anything odd about the games that were kept.
+#[derive(Debug, Default)]
+pub struct GameLog {
+	pub games: Vec<Game>,
+	pub errors: Vec<ParseError>,
+	pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug)]
+pub struct ParsedGame {
+	pub game: Game,
+	pub diagnostics: Vec<Diagnostic>,
+}
+
+// How strict to be with the cube counts of a log. Counts above `max_count` make a
+// line invalid; without a limit, any count that fits in a u32 is accepted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GameParser {
+	pub duplicates: DuplicatePolicy,
+	pub max_count: Option<u32>,
 }
 
 impl Game {
@@ -16,91 +91,268 @@ impl Game {
 		}
 	}
 
-	pub fn add_turn(&mut self, turn: GameTurn) {
+	pub fn add_turn(&mut self, turn: CubeSet) {
 		self.turns.push(turn);
 	}
 
-	pub fn is_possible(&self, world: (i32, i32, i32)) -> bool {
-		for turn in &self.turns {
-			let diffs = (world.0 - turn.0, world.1 - turn.1, world.2 - turn.2);
-			if diffs.0 < 0 || diffs.1 < 0 || diffs.2 < 0 {
-				return false;
-			}
-		}
-		true
+	pub fn is_possible(&self, world: &CubeSet) -> bool {
+		self.turns.iter().all(|turn| turn.is_subset_of(world))
 	}
 
-	pub fn calc_min_world(&self) -> (i32, i32, i32) {
-		let mut min_world = (0, 0, 0);
+	pub fn calc_min_world(&self) -> CubeSet {
+		self.turns
+			.iter()
+			.fold(CubeSet::new(), |min_world, turn| min_world.max(turn))
+	}
 
-		for turn in &self.turns {
-			if turn.0 > min_world.0 {
-				min_world.0 = turn.0;
-			}
-			if turn.1 > min_world.1 {
-				min_world.1 = turn.1;
-			}
-			if turn.2 > min_world.2 {
-				min_world.2 = turn.2;
-			}
+	pub fn from_game_line(line: &str) -> Result<Game, LineError> {
+		GameParser::default().parse_line(line).map(|parsed| parsed.game)
+	}
+}
+
+// The canonical form of a game line, which parses back to the same game.
+impl fmt::Display for Game {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "Game {}:", self.id)?;
+		for (index, turn) in self.turns.iter().enumerate() {
+			let separator = if index == 0 { " " } else { "; " };
+			write!(f, "{}{}", separator, turn)?;
 		}
+		Ok(())
+	}
+}
+
+impl FromStr for Game {
+	type Err = LineError;
 
-		min_world
+	fn from_str(line: &str) -> Result<Game, LineError> {
+		Game::from_game_line(line)
 	}
+}
+
+impl FromStr for CubeSet {
+	type Err = LineError;
 
-	pub fn from_game_line(line: &str) -> Game {
-		return game_parser::parse_game_line(line);
+	fn from_str(turn: &str) -> Result<CubeSet, LineError> {
+		game_parser::parse_turn(turn, &GameParser::default()).map(|(turn, _)| turn)
 	}
 }
 
-mod game_parser {
-	use super::*;
+impl GameParser {
+	pub fn new() -> GameParser {
+		GameParser::default()
+	}
 
-	pub fn parse_game_line(line: &str) -> Game {
-		let game_line_regex = Regex::new(r#"^Game (\d+): (.*)$"#).unwrap();
-		let captures = game_line_regex.captures(line).unwrap();
+	pub fn with_duplicates(self, duplicates: DuplicatePolicy) -> GameParser {
+		GameParser { duplicates, ..self }
+	}
 
-		if captures.len() == 0 {
-			panic!("Invalid game line: {}", line);
+	pub fn with_max_count(self, max_count: u32) -> GameParser {
+		GameParser {
+			max_count: Some(max_count),
+			..self
+		}
+	}
+
+	pub fn parse_line(&self, line: &str) -> Result<ParsedGame, LineError> {
+		game_parser::parse_game_line(line, self)
+	}
+
+	// Parses games one line at a time, so a log never has to be held in memory.
+	// Blank lines are skipped; line numbers count them anyway.
+	pub fn games<I, S>(&self, lines: I) -> impl Iterator<Item = Result<ParsedGame, ParseError>>
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		let parser = *self;
+		lines
+			.into_iter()
+			.enumerate()
+			.filter(|(_, line)| !line.as_ref().trim().is_empty())
+			.map(move |(index, line)| {
+				let line = line.as_ref();
+				parser.parse_line(line).map_err(|error| ParseError {
+					line_number: index + 1,
+					line: line.to_string(),
+					error,
+				})
+			})
+	}
+
+	// Every game of a log, or the first line that isn't one.
+	pub fn parse_file<I, S>(&self, lines: I) -> Result<Vec<Game>, ParseError>
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		self.games(lines).map(|parsed| parsed.map(|parsed| parsed.game)).collect()
+	}
+
+	pub fn parse_file_lenient<I, S>(&self, lines: I) -> GameLog
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		let mut log = GameLog::default();
+		for parsed in self.games(lines) {
+			match parsed {
+				Ok(parsed) => {
+					log.games.push(parsed.game);
+					log.diagnostics.extend(parsed.diagnostics);
+				}
+				Err(err) => log.errors.push(err),
+			}
 		}
+		log
+	}
+}
+
+pub fn parse_games<I, S>(lines: I) -> impl Iterator<Item = Result<Game, ParseError>>
+where
+	I: IntoIterator<Item = S>,
+	S: AsRef<str>,
+{
+	GameParser::default()
+		.games(lines)
+		.map(|parsed| parsed.map(|parsed| parsed.game))
+}
+
+pub fn parse_game_file<I, S>(lines: I) -> Result<Vec<Game>, ParseError>
+where
+	I: IntoIterator<Item = S>,
+	S: AsRef<str>,
+{
+	GameParser::default().parse_file(lines)
+}
+
+pub fn parse_game_file_lenient<I, S>(lines: I) -> GameLog
+where
+	I: IntoIterator<Item = S>,
+	S: AsRef<str>,
+{
+	GameParser::default().parse_file_lenient(lines)
+}
 
-		let game_id = captures.get(1).unwrap().as_str().parse::<u32>().unwrap();
+mod game_parser {
+	use super::*;
+	use std::num::IntErrorKind;
+	use std::sync::LazyLock;
+
+	// Compiled once, logs can have millions of lines.
+	static GAME_LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"^Game (\d+):(.*)$"#).unwrap());
+	static CUBES_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"^(-?\d+)\s+(\p{Alphabetic}+)$"#).unwrap());
+
+	pub fn parse_game_line(line: &str, parser: &GameParser) -> Result<ParsedGame, LineError> {
+		let captures = GAME_LINE_REGEX
+			.captures(line.trim())
+			.ok_or(LineError::MissingHeader)?;
+
+		let game_id = captures.get(1).unwrap().as_str();
+		let game_id = game_id
+			.parse::<u32>()
+			.map_err(|_| LineError::InvalidId(game_id.to_string()))?;
 		let game_turns = captures.get(2).unwrap().as_str();
 
 		let mut game_obj = Game::new(game_id);
+		let mut diagnostics = Vec::new();
+		let turns: Vec<&str> = game_turns.split(";").collect();
+
+		for (index, turn) in turns.iter().enumerate() {
+			let diagnostic = |kind| Diagnostic {
+				game_id,
+				turn: index + 1,
+				kind,
+			};
 
-		for turn in game_turns.split(";") {
 			if turn.trim().is_empty() {
+				// "Game 1: " has no turns rather than one empty one
+				if turns.len() > 1 {
+					diagnostics.push(diagnostic(DiagnosticKind::EmptyTurn));
+				}
 				continue;
 			}
-			let parsed_turn = parse_turn(turn);
+			let (parsed_turn, duplicates) = parse_turn(turn, parser)?;
+			diagnostics.extend(duplicates.into_iter().map(diagnostic));
 			game_obj.add_turn(parsed_turn);
 		}
 
-		game_obj
+		Ok(ParsedGame {
+			game: game_obj,
+			diagnostics,
+		})
 	}
 
-	pub fn parse_turn(turn: &str) -> GameTurn {
-		let mut turn_result = (0, 0, 0);
-		let turn_regex = Regex::new(r#"(\d+)\s(red|green|blue)"#).unwrap();
+	fn parse_count(count: &str, cubes: &str, max: u32) -> Result<u32, LineError> {
+		if count.starts_with('-') {
+			return Err(LineError::NegativeCount(cubes.to_string()));
+		}
+		let too_large = || LineError::CountTooLarge {
+			cubes: cubes.to_string(),
+			max,
+		};
+		// `\d` also matches digits of other scripts, which u32 can't parse
+		match count.parse::<u32>() {
+			Ok(count) if count <= max => Ok(count),
+			Ok(_) => Err(too_large()),
+			Err(err) if *err.kind() == IntErrorKind::PosOverflow => Err(too_large()),
+			Err(_) => Err(LineError::InvalidCubes(cubes.to_string())),
+		}
+	}
+
+	// The cubes of one turn, with a diagnostic for every color that was counted more
+	// than once.
+	pub fn parse_turn(turn: &str, parser: &GameParser) -> Result<(CubeSet, Vec<DiagnosticKind>), LineError> {
+		let mut turn_result = CubeSet::new();
+		let mut diagnostics = Vec::new();
+		let max = parser.max_count.unwrap_or(u32::MAX);
 
-		if turn.len() == 0 {
-			return turn_result;
+		if turn.trim().is_empty() {
+			return Ok((turn_result, diagnostics));
 		}
 
+		// colors in the order they first appear, with every count they got
+		let mut counts: Vec<(String, Vec<u32>)> = Vec::new();
 		for cubes in turn.split(",") {
-			let captures = turn_regex.captures(cubes).unwrap();
-			let num_cubes = captures.get(1).unwrap().as_str().parse::<i32>().unwrap();
-			let color = captures.get(2).unwrap().as_str();
-
-			match color {
-				"red"   => turn_result.0 = num_cubes,
-				"green" => turn_result.1 = num_cubes,
-				"blue"  => turn_result.2 = num_cubes,
-				_       => panic!("Invalid color: {}", color),
+			let cubes = cubes.trim();
+			let captures = CUBES_REGEX
+				.captures(cubes)
+				.ok_or_else(|| LineError::InvalidCubes(cubes.to_string()))?;
+			let num_cubes = parse_count(captures.get(1).unwrap().as_str(), cubes, max)?;
+			let color = captures.get(2).unwrap().as_str().to_lowercase();
+
+			match counts.iter_mut().find(|(seen, _)| *seen == color) {
+				Some((_, seen)) => seen.push(num_cubes),
+				None => counts.push((color, vec![num_cubes])),
 			}
 		}
-		turn_result
+
+		for (color, color_counts) in counts {
+			let resolved = parser
+				.duplicates
+				.resolve(&color_counts)
+				.ok_or_else(|| LineError::DuplicateColor {
+					turn: turn.trim().to_string(),
+					color: color.clone(),
+				})?;
+			let resolved = u32::try_from(resolved)
+				.ok()
+				.filter(|&resolved| resolved <= max)
+				.ok_or_else(|| LineError::CountTooLarge {
+					cubes: format!("{} {}", resolved, color),
+					max,
+				})?;
+
+			if color_counts.len() > 1 {
+				diagnostics.push(DiagnosticKind::DuplicateColor {
+					color: color.clone(),
+					counts: color_counts,
+					resolved,
+				});
+			}
+			turn_result.set(&color, resolved);
+		}
+		Ok((turn_result, diagnostics))
 	}
 }
 
@@ -108,21 +360,34 @@ mod game_parser {
 mod tests {
 	use super::*;
 
+	const EXAMPLE: &str = r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"#;
+
+	#[test]
+	fn test_snapshot_parsed_games() {
+		let games = parse_game_file(EXAMPLE.lines()).unwrap();
+
+		insta::assert_debug_snapshot!(games);
+	}
+
 	#[test]
 	fn test_parse_game_line() {
 		let line = "Game 1: 18 red, 8 green, 7 blue; 15 red, 4 blue, 1 green; 16 red, 5 green";
-		let game = game_parser::parse_game_line(line);
+		let game = Game::from_game_line(line).unwrap();
 
 		assert_eq!(game.id, 1);
-		assert_eq!(game.turns[0], (18, 8, 7));
-		assert_eq!(game.turns[1], (15, 1, 4));
-		assert_eq!(game.turns[2], (16, 5, 0));
+		assert_eq!(game.turns[0], CubeSet::rgb(18, 8, 7));
+		assert_eq!(game.turns[1], CubeSet::rgb(15, 1, 4));
+		assert_eq!(game.turns[2], CubeSet::rgb(16, 5, 0));
 	}
 
 	#[test]
 	fn test_parse_game_line_empty() {
 		let line = "Game 1: ; ; ";
-		let game = game_parser::parse_game_line(line);
+		let game = Game::from_game_line(line).unwrap();
 
 		assert_eq!(game.id, 1);
 		assert_eq!(game.turns.len(), 0);
@@ -131,7 +396,7 @@ mod tests {
 	#[test]
 	fn test_parse_game_line_no_turns() {
 		let line = "Game 1: ";
-		let game = game_parser::parse_game_line(line);
+		let game = Game::from_game_line(line).unwrap();
 
 		assert_eq!(game.id, 1);
 		assert_eq!(game.turns.len(), 0);
@@ -139,39 +404,40 @@ mod tests {
 
 	#[test]
 	fn test_parse_turn() {
-		let sample_turn = game_parser::parse_turn("18 red, 8 green, 7 blue");
-		let empty_turn1 = game_parser::parse_turn("0 red, 0 green, 0 blue");
-		let empty_turn2 = game_parser::parse_turn("");
-
-		assert_eq!(sample_turn, (18, 8, 7));
-		assert_eq!(empty_turn1, (0, 0, 0));
-		assert_eq!(empty_turn2, (0, 0, 0));
+		let parse_turn = |turn| game_parser::parse_turn(turn, &GameParser::default()).unwrap().0;
+		let sample_turn = parse_turn("18 red, 8 green, 7 blue");
+		let empty_turn1 = parse_turn("0 red, 0 green, 0 blue");
+		let empty_turn2 = parse_turn("");
+
+		assert_eq!(sample_turn, CubeSet::rgb(18, 8, 7));
+		assert_eq!(empty_turn1, CubeSet::new());
+		assert_eq!(empty_turn2, CubeSet::new());
 	}
 
 	#[test]
 	fn test_check_game_possible() {
-		let world = (15, 11, 10);
+		let world = CubeSet::rgb(15, 11, 10);
 		let game1 = Game {
 			id: 1,
 			turns: vec![
-				(14, 8, 7),
-				(15, 11, 4),
-				(12, 5, 9),
+				CubeSet::rgb(14, 8, 7),
+				CubeSet::rgb(15, 11, 4),
+				CubeSet::rgb(12, 5, 9),
 			],
 		};
 
-		assert_eq!(game1.is_possible(world), true);
+		assert!(game1.is_possible(&world));
 
 		let game2 = Game {
 			id: 2,
 			turns: vec![
-				(16, 8, 7),
-				(15, 11, 4),
-				(12, 5, 9),
+				CubeSet::rgb(16, 8, 7),
+				CubeSet::rgb(15, 11, 4),
+				CubeSet::rgb(12, 5, 9),
 			],
 		};
 
-		assert_eq!(game2.is_possible(world), false);
+		assert!(!game2.is_possible(&world));
 	}
 
 	#[test]
@@ -179,11 +445,169 @@ mod tests {
 		let game1 = Game {
 			id: 1,
 			turns: vec![
-				(14, 8, 7),
-				(15, 11, 4),
-				(12, 5, 9),
+				CubeSet::rgb(14, 8, 7),
+				CubeSet::rgb(15, 11, 4),
+				CubeSet::rgb(12, 5, 9),
 			],
 		};
-		assert_eq!(game1.calc_min_world(), (15, 11, 9));
+		assert_eq!(game1.calc_min_world(), CubeSet::rgb(15, 11, 9));
+	}
+
+	#[test]
+	fn test_other_colors() {
+		let game = Game::from_game_line("Game 7: 2 Purple, 1 red; 3 yellow, 1 purple").unwrap();
+		let bag: CubeSet = [("red", 1), ("purple", 2), ("yellow", 3)].into_iter().collect();
+
+		assert_eq!(game.calc_min_world(), bag);
+		assert!(game.is_possible(&bag));
+		assert!(!game.is_possible(&CubeSet::rgb(12, 13, 14)));
+		assert_eq!(game.calc_min_world().product_of(&["purple", "yellow"]), Some(6));
+	}
+
+	#[test]
+	fn test_parse_errors() {
+		assert_eq!(Game::from_game_line("Gme 1: 3 red").unwrap_err(), LineError::MissingHeader);
+		assert_eq!(
+			Game::from_game_line("Game 99999999999: 3 red").unwrap_err(),
+			LineError::InvalidId("99999999999".to_string())
+		);
+		assert_eq!(
+			Game::from_game_line("Game 1: 3 red; 4 red blue").unwrap_err(),
+			LineError::InvalidCubes("4 red blue".to_string())
+		);
+		assert_eq!(
+			Game::from_game_line("Game 1: 3 red, , 2 blue").unwrap_err(),
+			LineError::InvalidCubes("".to_string())
+		);
+	}
+
+	#[test]
+	fn test_parse_game_file() {
+		let log = "Game 1: 3 blue\n\nGame 2: lots of red\nGame 3: 1 red\nGame three: 2 red";
+
+		let err = parse_game_file(log.lines()).unwrap_err();
+		assert_eq!(err.line_number, 3);
+		assert_eq!(err.error, LineError::InvalidCubes("lots of red".to_string()));
+		assert_eq!(
+			err.to_string(),
+			"line 3: invalid cubes 'lots of red', expected a count and a color like '3 blue' in \"Game 2: lots of red\""
+		);
+
+		let lenient = parse_game_file_lenient(log.lines());
+		let ids: Vec<u32> = lenient.games.iter().map(|game| game.id).collect();
+		let lines: Vec<usize> = lenient.errors.iter().map(|err| err.line_number).collect();
+		assert_eq!(ids, vec![1, 3]);
+		assert_eq!(lines, vec![3, 5]);
+		assert_eq!(lenient.errors[1].error, LineError::MissingHeader);
+	}
+
+	#[test]
+	fn test_duplicate_colors() {
+		let line = "Game 4: 3 red, 2 red, 1 blue; 5 green";
+		let sum = GameParser::new().parse_line(line).unwrap();
+		let max = GameParser::new()
+			.with_duplicates(DuplicatePolicy::Max)
+			.parse_line(line)
+			.unwrap();
+
+		assert_eq!(sum.game.turns[0], CubeSet::rgb(5, 0, 1));
+		assert_eq!(max.game.turns[0], CubeSet::rgb(3, 0, 1));
+		assert_eq!(
+			sum.diagnostics,
+			vec![Diagnostic {
+				game_id: 4,
+				turn: 1,
+				kind: DiagnosticKind::DuplicateColor {
+					color: "red".to_string(),
+					counts: vec![3, 2],
+					resolved: 5,
+				},
+			}]
+		);
+		assert_eq!(
+			GameParser::new()
+				.with_duplicates(DuplicatePolicy::Reject)
+				.parse_line(line)
+				.unwrap_err(),
+			LineError::DuplicateColor {
+				turn: "3 red, 2 red, 1 blue".to_string(),
+				color: "red".to_string(),
+			}
+		);
+	}
+
+	#[test]
+	fn test_count_limits() {
+		let parser = GameParser::new().with_max_count(100);
+
+		assert_eq!(
+			parser.parse_line("Game 1: -3 red").unwrap_err(),
+			LineError::NegativeCount("-3 red".to_string())
+		);
+		assert_eq!(
+			parser.parse_line("Game 1: 3 red; 101 blue").unwrap_err(),
+			LineError::CountTooLarge {
+				cubes: "101 blue".to_string(),
+				max: 100,
+			}
+		);
+		assert_eq!(
+			parser.parse_line("Game 1: 60 red, 60 red").unwrap_err(),
+			LineError::CountTooLarge {
+				cubes: "120 red".to_string(),
+				max: 100,
+			}
+		);
+		assert_eq!(
+			Game::from_game_line("Game 1: 99999999999 red").unwrap_err(),
+			LineError::CountTooLarge {
+				cubes: "99999999999 red".to_string(),
+				max: u32::MAX,
+			}
+		);
+		assert!(parser.parse_line("Game 1: 100 red").is_ok());
+	}
+
+	#[test]
+	fn test_non_ascii_digits() {
+		assert_eq!(
+			Game::from_game_line("Game 1: ٣ red").unwrap_err(),
+			LineError::InvalidCubes("٣ red".to_string())
+		);
+		assert_eq!(
+			Game::from_game_line("Game 1: 1٣ red").unwrap_err(),
+			LineError::InvalidCubes("1٣ red".to_string())
+		);
+	}
+
+	#[test]
+	fn test_lenient_diagnostics() {
+		let log = "Game 1: 3 blue;; 1 blue, 1 blue\nGame 2: -1 red\nGame 3: 1 red";
+		let log = GameParser::new().parse_file_lenient(log.lines());
+
+		assert_eq!(log.games.len(), 2);
+		assert_eq!(log.errors[0].line_number, 2);
+		assert_eq!(
+			log.diagnostics.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+			vec![
+				"game 1, turn 2: empty turn skipped",
+				"game 1, turn 3: blue is counted 2 times (1, 1), using 2",
+			]
+		);
+	}
+
+	#[test]
+	fn test_display_round_trip() {
+		let games = parse_game_file(EXAMPLE.lines()).unwrap();
+		let formatted: Vec<String> = games.iter().map(|game| game.to_string()).collect();
+
+		assert_eq!(formatted[0], "Game 1: 4 red, 3 blue; 1 red, 2 green, 6 blue; 2 green");
+		assert_eq!(parse_game_file(&formatted).unwrap(), games);
+
+		let odd = Game::from_game_line("Game 9: 0 red; 2 Purple, 1 blue, 1 purple").unwrap();
+		assert_eq!(odd.to_string(), "Game 9: 0 red, 0 green, 0 blue; 1 blue, 3 purple");
+		assert_eq!(odd.to_string().parse::<Game>().unwrap(), odd);
+		assert_eq!(Game::new(3).to_string(), "Game 3:");
+		assert_eq!(Game::new(3).to_string().parse::<Game>().unwrap(), Game::new(3));
 	}
 }
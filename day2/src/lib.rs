@@ -1 +1,10 @@
+pub mod cli;
+pub mod cubes;
+pub mod estimate;
+pub mod export;
+pub mod filter;
+pub mod frontier;
 pub mod game;
+pub mod report;
+pub mod simulate;
+pub mod validation;
@@ -0,0 +1,322 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::cubes::CubeSet;
+use crate::game::Game;
+
+pub const PERCENTILES: [u32; 4] = [25, 50, 75, 90];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+	pub count: usize,
+	pub mean: f64,
+	pub min: u64,
+	pub max: u64,
+	// the value for each of PERCENTILES
+	pub percentiles: BTreeMap<u32, u64>,
+}
+
+impl Summary {
+	// Percentiles use the nearest rank, so they are always one of the values.
+	pub fn new(mut values: Vec<u64>) -> Summary {
+		values.sort_unstable();
+		let count = values.len();
+		let percentile = |p: u32| match count {
+			0 => 0,
+			_ => values[((p as usize * count).div_ceil(100)).max(1) - 1],
+		};
+
+		Summary {
+			count,
+			mean: match count {
+				0 => 0.0,
+				// summed in u128 so a few huge values can't overflow
+				_ => values.iter().map(|&value| value as u128).sum::<u128>() as f64 / count as f64,
+			},
+			min: values.first().copied().unwrap_or(0),
+			max: values.last().copied().unwrap_or(0),
+			percentiles: PERCENTILES.iter().map(|&p| (p, percentile(p))).collect(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColorStats {
+	pub color: String,
+	// cubes of this color shown per turn, counting turns without any as 0
+	pub per_turn: Summary,
+}
+
+// Games with a power from `from` up to and including `to`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Bucket {
+	pub from: u64,
+	pub to: u64,
+	pub games: usize,
+}
+
+// The games that are possible with the reference bag but not with `bag`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Sensitivity {
+	pub bag: CubeSet,
+	pub lost: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+	pub games: usize,
+	pub turns: usize,
+	pub colors: Vec<ColorStats>,
+	pub powers: Summary,
+	pub power_histogram: Vec<Bucket>,
+	pub bag: CubeSet,
+	pub possible: usize,
+	pub smaller_bags: Vec<Sensitivity>,
+}
+
+const HISTOGRAM_BUCKETS: u64 = 10;
+
+fn histogram(powers: &[u64]) -> Vec<Bucket> {
+	let Some(&max) = powers.iter().max() else {
+		return Vec::new();
+	};
+	let width = (max / HISTOGRAM_BUCKETS + 1).max(1);
+
+	(0..=max / width)
+		.map(|index| {
+			let from = index * width;
+			let to = from.saturating_add(width - 1);
+			Bucket {
+				from,
+				to,
+				games: powers.iter().filter(|&&power| (from..=to).contains(&power)).count(),
+			}
+		})
+		.collect()
+}
+
+// The reference bag with one cube less of each of its colors in turn, then with one
+// cube less of every color at once.
+fn smaller_bags(bag: &CubeSet) -> Vec<CubeSet> {
+	let mut bags: Vec<CubeSet> = bag
+		.nonzero()
+		.map(|(color, count)| {
+			let mut smaller = bag.clone();
+			smaller.set(color, count - 1);
+			smaller
+		})
+		.collect();
+
+	if bags.len() > 1 {
+		let mut all = bag.clone();
+		for (color, count) in bag.nonzero() {
+			all.set(color, count - 1);
+		}
+		bags.push(all);
+	}
+	bags
+}
+
+impl Report {
+	// Describes a log, with `bag` as the bag the possible games are checked against.
+	pub fn new(games: &[Game], bag: &CubeSet) -> Report {
+		let turns: Vec<&CubeSet> = games.iter().flat_map(|game| &game.turns).collect();
+		let seen = turns.iter().fold(CubeSet::rgb(0, 0, 0), |seen, turn| seen.max(turn));
+		let colors = seen
+			.colors()
+			.map(|(color, _)| ColorStats {
+				color: color.to_string(),
+				per_turn: Summary::new(turns.iter().map(|turn| turn.count(color) as u64).collect()),
+			})
+			.collect();
+
+		// powers too large for a u64 are counted as u64::MAX
+		let powers: Vec<u64> = games
+			.iter()
+			.map(|game| game.calc_min_world().product().unwrap_or(u64::MAX))
+			.collect();
+		let possible: Vec<&Game> = games.iter().filter(|game| game.is_possible(bag)).collect();
+		let smaller_bags = smaller_bags(bag)
+			.into_iter()
+			.map(|smaller| Sensitivity {
+				lost: possible
+					.iter()
+					.filter(|game| !game.is_possible(&smaller))
+					.map(|game| game.id)
+					.collect(),
+				bag: smaller,
+			})
+			.collect();
+
+		Report {
+			games: games.len(),
+			turns: turns.len(),
+			colors,
+			power_histogram: histogram(&powers),
+			powers: Summary::new(powers),
+			bag: bag.clone(),
+			possible: possible.len(),
+			smaller_bags,
+		}
+	}
+
+	pub fn to_json(&self) -> String {
+		serde_json::to_string_pretty(self).expect("reports only hold plain data")
+	}
+
+	pub fn table(&self) -> String {
+		let mut table = String::new();
+		let percentile_names: Vec<String> = PERCENTILES.iter().map(|p| format!("p{}", p)).collect();
+		let row = |name: &str, summary: &Summary| {
+			let percentiles: Vec<String> = summary
+				.percentiles
+				.values()
+				.map(|value| format!("{:>8}", value))
+				.collect();
+			format!(
+				"{:<10}{:>8.2}{:>8}{:>8}{}\n",
+				name,
+				summary.mean,
+				summary.min,
+				summary.max,
+				percentiles.join("")
+			)
+		};
+
+		writeln!(table, "{} games, {} turns", self.games, self.turns).unwrap();
+		writeln!(table).unwrap();
+		let header: Vec<String> = percentile_names.iter().map(|name| format!("{:>8}", name)).collect();
+		writeln!(table, "{:<10}{:>8}{:>8}{:>8}{}", "per turn", "mean", "min", "max", header.join("")).unwrap();
+		for stats in &self.colors {
+			table.push_str(&row(&stats.color, &stats.per_turn));
+		}
+		table.push_str(&row("power", &self.powers));
+
+		writeln!(table).unwrap();
+		writeln!(table, "{:<22}{:>8}", "power", "games").unwrap();
+		for bucket in &self.power_histogram {
+			let range = format!("{}..={}", bucket.from, bucket.to);
+			writeln!(table, "{:<22}{:>8}  {}", range, bucket.games, "#".repeat(bucket.games.min(60))).unwrap();
+		}
+
+		writeln!(table).unwrap();
+		writeln!(table, "{} of {} games are possible with {}", self.possible, self.games, self.bag).unwrap();
+		for sensitivity in &self.smaller_bags {
+			let lost: Vec<String> = sensitivity.lost.iter().map(|id| id.to_string()).collect();
+			writeln!(
+				table,
+				"with {}: {} fewer ({})",
+				sensitivity.bag,
+				lost.len(),
+				if lost.is_empty() { "none".to_string() } else { lost.join(", ") }
+			)
+			.unwrap();
+		}
+
+		table
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::game::parse_game_file;
+
+	const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+	fn report() -> Report {
+		Report::new(&parse_game_file(EXAMPLE.lines()).unwrap(), &CubeSet::rgb(12, 13, 14))
+	}
+
+	#[test]
+	fn test_summary() {
+		let summary = Summary::new(vec![5, 1, 4, 2, 3, 10, 7, 6, 9, 8]);
+
+		assert_eq!((summary.count, summary.min, summary.max), (10, 1, 10));
+		assert_eq!(summary.mean, 5.5);
+		assert_eq!(summary.percentiles, BTreeMap::from([(25, 3), (50, 5), (75, 8), (90, 9)]));
+		assert_eq!(Summary::new(vec![]).percentiles[&90], 0);
+	}
+
+	#[test]
+	fn test_huge_values() {
+		let summary = Summary::new(vec![u64::MAX, u64::MAX, 1]);
+		assert_eq!(summary.max, u64::MAX);
+		assert!((summary.mean - 2.0 * u64::MAX as f64 / 3.0).abs() < 1e4);
+
+		let games = parse_game_file([
+			"Game 1: 300 red, 300 green, 300 blue, 300 d, 300 e, 300 f, 300 g, 300 h",
+			"Game 2: 1 red, 1 green, 1 blue",
+		])
+		.unwrap();
+		let report = Report::new(&games, &CubeSet::rgb(12, 13, 14));
+		assert_eq!((report.powers.min, report.powers.max), (1, u64::MAX));
+		assert_eq!(report.power_histogram.last().unwrap().to, u64::MAX);
+		assert_eq!(report.power_histogram.iter().map(|bucket| bucket.games).sum::<usize>(), 2);
+	}
+
+	#[test]
+	fn test_color_stats() {
+		let report = report();
+		let red = &report.colors[0];
+
+		assert_eq!(report.turns, 14);
+		assert_eq!(red.color, "red");
+		// red per turn: 4 1 0, 0 1 0, 20 4 1, 3 6 14, 6 1
+		assert_eq!((red.per_turn.max, red.per_turn.percentiles[&50]), (20, 1));
+		assert!((red.per_turn.mean - 61.0 / 14.0).abs() < 1e-9);
+		assert_eq!(report.colors.len(), 3);
+	}
+
+	#[test]
+	fn test_powers() {
+		let report = report();
+
+		assert_eq!((report.powers.min, report.powers.max), (12, 1560));
+		assert_eq!(report.power_histogram.len(), 10);
+		assert_eq!(report.power_histogram[0], Bucket { from: 0, to: 156, games: 3 });
+		assert_eq!(report.power_histogram.iter().map(|bucket| bucket.games).sum::<usize>(), 5);
+	}
+
+	#[test]
+	fn test_powers_of_other_colors() {
+		let games = parse_game_file(["Game 1: 2 purple, 3 teal", "Game 2: 4 red; 5 blue"]).unwrap();
+		let report = Report::new(&games, &CubeSet::rgb(12, 13, 14));
+
+		assert_eq!((report.powers.min, report.powers.max), (6, 20));
+	}
+
+	#[test]
+	fn test_smaller_bags() {
+		let report = report();
+
+		assert_eq!(report.possible, 3);
+		assert_eq!(report.smaller_bags.len(), 4);
+		assert!(report.smaller_bags.iter().all(|smaller| smaller.lost.is_empty()));
+
+		let tight = Report::new(&parse_game_file(EXAMPLE.lines()).unwrap(), &CubeSet::rgb(6, 3, 6));
+		let lost: Vec<Vec<u32>> = tight.smaller_bags.iter().map(|smaller| smaller.lost.clone()).collect();
+		assert_eq!(lost, vec![vec![5], vec![2, 5], vec![1], vec![1, 2, 5]]);
+	}
+
+	#[test]
+	fn test_outputs() {
+		let report = report();
+		let table = report.table();
+		let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+
+		assert!(table.starts_with("5 games, 14 turns\n"));
+		assert!(table.contains("3 of 5 games are possible with 12 red, 13 green, 14 blue"));
+		assert!(table.contains("with 11 red, 13 green, 14 blue: 0 fewer (none)"));
+		assert_eq!(json["games"], 5);
+		assert_eq!(json["colors"][2]["color"], "blue");
+		assert_eq!(json["bag"]["green"], 13);
+		assert_eq!(json["powers"]["percentiles"]["50"], 48);
+	}
+}
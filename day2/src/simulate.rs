@@ -0,0 +1,197 @@
+use std::ops::RangeInclusive;
+
+use crate::cubes::CubeSet;
+use crate::export;
+use crate::game::Game;
+
+// A small seeded generator (SplitMix64), so the same seed always gives the same log.
+struct Rng(u64);
+
+impl Rng {
+	fn next(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+		z ^ (z >> 31)
+	}
+
+	// A number in `range`. The modulo bias is far too small to matter for ranges the
+	// size of a bag.
+	fn in_range(&mut self, range: RangeInclusive<u64>) -> u64 {
+		let span = range.end() - range.start() + 1;
+		range.start() + self.next() % span
+	}
+}
+
+// Plays games with a known bag: every turn draws a handful of cubes without
+// replacement, shows them and puts them back before the next turn.
+#[derive(Debug, Clone)]
+pub struct Simulator {
+	pub bag: CubeSet,
+	pub seed: u64,
+	pub turns_per_game: usize,
+	// how many cubes a turn draws, capped at the size of the bag
+	pub draw_sizes: RangeInclusive<u32>,
+}
+
+impl Simulator {
+	pub fn new(bag: CubeSet, seed: u64) -> Simulator {
+		let size = bag.total();
+		Simulator {
+			bag,
+			seed,
+			turns_per_game: 3,
+			draw_sizes: 1..=size,
+		}
+	}
+
+	pub fn with_turns(self, turns_per_game: usize) -> Simulator {
+		Simulator { turns_per_game, ..self }
+	}
+
+	pub fn with_draw_sizes(self, draw_sizes: RangeInclusive<u32>) -> Simulator {
+		Simulator { draw_sizes, ..self }
+	}
+
+	// Games numbered from 1.
+	pub fn games(&self, count: usize) -> Vec<Game> {
+		let mut rng = Rng(self.seed);
+		let colors: Vec<(&str, u32)> = self.bag.nonzero().collect();
+		let largest = (*self.draw_sizes.end()).min(self.bag.total());
+		let smallest = (*self.draw_sizes.start()).min(largest);
+
+		(1..=count)
+			.map(|id| {
+				let mut game = Game::new(id as u32);
+				for _ in 0..self.turns_per_game {
+					let size = rng.in_range(smallest as u64..=largest as u64) as u32;
+					game.add_turn(draw(&colors, size, &mut rng));
+				}
+				game
+			})
+			.collect()
+	}
+
+	// The same games as a game log, one `Game N: ...` line each.
+	pub fn log(&self, count: usize) -> String {
+		export::to_text(&self.games(count))
+	}
+}
+
+// Draws `size` different cubes one at a time, picking each color with a chance
+// proportional to how many of its cubes are still in the bag. Only the counts are
+// kept, so a bag of billions of cubes costs no more memory than a small one.
+fn draw(colors: &[(&str, u32)], size: u32, rng: &mut Rng) -> CubeSet {
+	let mut left: Vec<u64> = colors.iter().map(|&(_, count)| count as u64).collect();
+	let mut total: u64 = left.iter().sum();
+
+	// taking most of the bag is the same as picking the few cubes that stay behind
+	if size as u64 > total / 2 {
+		let stay = draw(colors, (total - size as u64) as u32, rng);
+		return colors.iter().map(|&(color, count)| (color, count - stay.count(color))).collect();
+	}
+
+	let mut drawn = vec![0u32; colors.len()];
+
+	for taken in 0..size {
+		// once one color holds every cube left, the rest of the draw is that color
+		if let Some(index) = left.iter().position(|&count| count == total) {
+			drawn[index] += size - taken;
+			break;
+		}
+
+		let mut pick = rng.in_range(0..=total - 1);
+		let index = left
+			.iter()
+			.position(|&count| match pick.checked_sub(count) {
+				Some(rest) => {
+					pick = rest;
+					false
+				}
+				None => true,
+			})
+			.expect("pick is below the cubes left");
+		left[index] -= 1;
+		total -= 1;
+		drawn[index] += 1;
+	}
+
+	colors.iter().zip(drawn).map(|(&(color, _), count)| (color, count)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::estimate::BagSize;
+	use crate::game::parse_game_file;
+
+	#[test]
+	fn test_same_seed_same_games() {
+		let simulator = Simulator::new(CubeSet::rgb(12, 13, 14), 7);
+
+		assert_eq!(simulator.games(20), simulator.games(20));
+		assert_ne!(simulator.games(20), Simulator::new(CubeSet::rgb(12, 13, 14), 8).games(20));
+	}
+
+	#[test]
+	fn test_games_fit_the_bag() {
+		let bag: CubeSet = [("red", 5), ("green", 2), ("purple", 3)].into_iter().collect();
+		let simulator = Simulator::new(bag.clone(), 2023)
+			.with_turns(4)
+			.with_draw_sizes(2..=6);
+		let games = simulator.games(200);
+
+		assert_eq!(games.len(), 200);
+		assert_eq!(games[199].id, 200);
+		for game in &games {
+			assert_eq!(game.turns.len(), 4);
+			assert!(game.is_possible(&bag));
+			assert!(game.turns.iter().all(|turn| (2..=6).contains(&turn.total())));
+		}
+		// every cube of every color turns up at some point
+		let seen = games.iter().fold(CubeSet::new(), |seen, game| seen.max(&game.calc_min_world()));
+		assert_eq!(seen.max(&bag), bag);
+	}
+
+	#[test]
+	fn test_draw_sizes_are_capped() {
+		let games = Simulator::new(CubeSet::rgb(1, 1, 0), 1)
+			.with_draw_sizes(5..=9)
+			.games(3);
+
+		assert!(games.iter().flat_map(|game| &game.turns).all(|turn| *turn == CubeSet::rgb(1, 1, 0)));
+		assert!(Simulator::new(CubeSet::new(), 1).games(2).iter().all(|game| game.turns.iter().all(CubeSet::is_empty)));
+	}
+
+	#[test]
+	fn test_huge_bags() {
+		let bag: CubeSet = "2000000000 red, 1 blue".parse().unwrap();
+		let games = Simulator::new(bag.clone(), 5).with_draw_sizes(1..=3).games(10);
+
+		assert!(games.iter().all(|game| game.is_possible(&bag)));
+		assert!(games.iter().flat_map(|game| &game.turns).all(|turn| (1..=3).contains(&turn.total())));
+
+		let everything = Simulator::new(bag.clone(), 5).with_draw_sizes(u32::MAX..=u32::MAX).games(1);
+		assert_eq!(everything[0].turns[0], bag);
+	}
+
+	#[test]
+	fn test_log_parses_back() {
+		let simulator = Simulator::new(CubeSet::rgb(12, 13, 14), 99).with_turns(5);
+
+		assert_eq!(parse_game_file(simulator.log(50).lines()).unwrap(), simulator.games(50));
+	}
+
+	#[test]
+	fn test_estimate_recovers_bag() {
+		let bag = CubeSet::rgb(6, 3, 1);
+		let games = Simulator::new(bag.clone(), 11)
+			.with_turns(20)
+			.with_draw_sizes(1..=3)
+			.games(100);
+
+		let estimate = crate::estimate::estimate_common_bag(&games, BagSize::Exactly(10)).unwrap();
+		assert_eq!(estimate.bag, bag);
+	}
+}
@@ -0,0 +1,92 @@
+use std::fmt;
+
+use clap::ValueEnum;
+
+// What to do when a turn names the same color more than once, as in "3 red, 2 red".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum DuplicatePolicy {
+	// the cubes were shown in two handfuls, so count them all
+	#[default]
+	Sum,
+	// the counts describe the same cubes, so keep the largest
+	Max,
+	// the line is invalid
+	Reject,
+}
+
+impl DuplicatePolicy {
+	// Combines the counts a color got in one turn, or None when repeats are rejected.
+	pub fn resolve(self, counts: &[u32]) -> Option<u64> {
+		match self {
+			DuplicatePolicy::Sum => Some(counts.iter().map(|&count| count as u64).sum()),
+			DuplicatePolicy::Max => counts.iter().max().map(|&count| count as u64),
+			DuplicatePolicy::Reject if counts.len() > 1 => None,
+			DuplicatePolicy::Reject => counts.first().map(|&count| count as u64),
+		}
+	}
+}
+
+// Something odd about a game that still let it be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticKind {
+	DuplicateColor {
+		color: String,
+		counts: Vec<u32>,
+		resolved: u32,
+	},
+	EmptyTurn,
+}
+
+// `turn` counts from 1 and includes empty turns, so it matches the position in the line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+	pub game_id: u32,
+	pub turn: usize,
+	pub kind: DiagnosticKind,
+}
+
+impl fmt::Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "game {}, turn {}: ", self.game_id, self.turn)?;
+		match &self.kind {
+			DiagnosticKind::DuplicateColor {
+				color,
+				counts,
+				resolved,
+			} => {
+				let counts: Vec<String> = counts.iter().map(|count| count.to_string()).collect();
+				write!(f, "{} is counted {} times ({}), using {}", color, counts.len(), counts.join(", "), resolved)
+			}
+			DiagnosticKind::EmptyTurn => write!(f, "empty turn skipped"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_resolve_duplicates() {
+		assert_eq!(DuplicatePolicy::Sum.resolve(&[3, 2]), Some(5));
+		assert_eq!(DuplicatePolicy::Max.resolve(&[3, 2]), Some(3));
+		assert_eq!(DuplicatePolicy::Reject.resolve(&[3, 2]), None);
+		assert_eq!(DuplicatePolicy::Reject.resolve(&[3]), Some(3));
+		assert_eq!(DuplicatePolicy::Sum.resolve(&[u32::MAX, 1]), Some(u32::MAX as u64 + 1));
+	}
+
+	#[test]
+	fn test_display() {
+		let diagnostic = Diagnostic {
+			game_id: 4,
+			turn: 2,
+			kind: DiagnosticKind::DuplicateColor {
+				color: "red".to_string(),
+				counts: vec![3, 2],
+				resolved: 5,
+			},
+		};
+
+		assert_eq!(diagnostic.to_string(), "game 4, turn 2: red is counted 2 times (3, 2), using 5");
+	}
+}
@@ -0,0 +1,20 @@
+use day3::schematic::Schematic;
+use day3::visualization::schematic_frame;
+use runner::Runner;
+
+fn main() {
+    println!("Part 1!");
+    let mut runner = Runner::from_args();
+    let data = runner.load_input(env!("CARGO_MANIFEST_DIR"));
+    let schematic = Schematic::parse(&data);
+
+    runner.visualize("schematic", || schematic_frame(&schematic));
+
+    let part_numbers_sum = schematic
+        .part_numbers()
+        .iter()
+        .map(|number| number.value)
+        .sum::<u32>();
+
+    println!("Sum of part numbers: {}", part_numbers_sum);
+}
@@ -0,0 +1,2 @@
+pub mod schematic;
+pub mod visualization;
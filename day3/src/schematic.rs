@@ -0,0 +1,161 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchematicNumber {
+    pub value: u32,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug)]
+pub struct Schematic {
+    pub grid: Vec<Vec<char>>,
+}
+
+impl Schematic {
+    pub fn parse(data: &str) -> Schematic {
+        let grid = data
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().collect())
+            .collect();
+
+        Schematic { grid }
+    }
+
+    pub fn is_symbol(c: char) -> bool {
+        !c.is_ascii_digit() && c != '.'
+    }
+
+    pub fn numbers(&self) -> Vec<SchematicNumber> {
+        let mut numbers = Vec::new();
+
+        for (row, line) in self.grid.iter().enumerate() {
+            let mut col = 0;
+
+            while col < line.len() {
+                if !line[col].is_ascii_digit() {
+                    col += 1;
+                    continue;
+                }
+
+                let start = col;
+                while col < line.len() && line[col].is_ascii_digit() {
+                    col += 1;
+                }
+                let value = line[start..col]
+                    .iter()
+                    .collect::<String>()
+                    .parse::<u32>()
+                    .unwrap();
+
+                numbers.push(SchematicNumber {
+                    value,
+                    row,
+                    start,
+                    end: col,
+                });
+            }
+        }
+
+        numbers
+    }
+
+    // a number is a part number when any cell around it (diagonals included) holds a symbol
+    pub fn is_part_number(&self, number: &SchematicNumber) -> bool {
+        let first_row = number.row.saturating_sub(1);
+        let last_row = (number.row + 1).min(self.grid.len() - 1);
+
+        (first_row..=last_row).any(|row| {
+            let line = &self.grid[row];
+            let first_col = number.start.saturating_sub(1);
+            let last_col = (number.end + 1).min(line.len());
+
+            line[first_col.min(last_col)..last_col]
+                .iter()
+                .any(|&c| Schematic::is_symbol(c))
+        })
+    }
+
+    pub fn part_numbers(&self) -> Vec<SchematicNumber> {
+        self.numbers()
+            .into_iter()
+            .filter(|number| self.is_part_number(number))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+"#;
+
+    #[test]
+    fn test_numbers() {
+        let schematic = Schematic::parse(EXAMPLE);
+        let numbers = schematic.numbers();
+
+        assert_eq!(numbers.len(), 10);
+        assert_eq!(
+            numbers[0],
+            SchematicNumber {
+                value: 467,
+                row: 0,
+                start: 0,
+                end: 3
+            }
+        );
+        assert_eq!(
+            numbers[3],
+            SchematicNumber {
+                value: 633,
+                row: 2,
+                start: 6,
+                end: 9
+            }
+        );
+    }
+
+    #[test]
+    fn test_is_symbol() {
+        assert!(Schematic::is_symbol('*'));
+        assert!(Schematic::is_symbol('#'));
+        assert!(!Schematic::is_symbol('.'));
+        assert!(!Schematic::is_symbol('7'));
+    }
+
+    #[test]
+    fn test_part_numbers() {
+        let schematic = Schematic::parse(EXAMPLE);
+        let part_numbers: Vec<u32> = schematic
+            .part_numbers()
+            .iter()
+            .map(|number| number.value)
+            .collect();
+
+        assert_eq!(part_numbers, vec![467, 35, 633, 617, 592, 755, 664, 598]);
+        assert_eq!(part_numbers.iter().sum::<u32>(), 4361);
+    }
+
+    #[test]
+    fn test_part_number_at_line_edge() {
+        let schematic = Schematic::parse("..12\n...*\n5...\n");
+        let part_numbers: Vec<u32> = schematic
+            .part_numbers()
+            .iter()
+            .map(|number| number.value)
+            .collect();
+
+        assert_eq!(part_numbers, vec![12]);
+    }
+}
@@ -0,0 +1,49 @@
+use visualize::{Color, Frame, Style};
+
+use crate::schematic::Schematic;
+
+// Draws the whole schematic: part numbers in green, numbers that touch no symbol in red,
+// symbols in yellow and the empty cells dimmed.
+pub fn schematic_frame(schematic: &Schematic) -> Frame {
+    let mut frame = Frame::new("Day 3: engine schematic");
+
+    for line in &schematic.grid {
+        let text: String = line.iter().collect();
+        frame.push_line(&text, Style::color(Color::Gray));
+
+        let row = frame.height() - 1;
+        for (col, &c) in line.iter().enumerate() {
+            if Schematic::is_symbol(c) {
+                frame.style_range(row, col..col + 1, Style::color(Color::Yellow).bold());
+            }
+        }
+    }
+
+    for number in schematic.numbers() {
+        let style = if schematic.is_part_number(&number) {
+            Style::color(Color::Green).bold()
+        } else {
+            Style::color(Color::Red)
+        };
+        frame.style_range(number.row, number.start..number.end, style);
+    }
+
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schematic_frame() {
+        let schematic = Schematic::parse("467..114..\n...*......\n");
+        let frame = schematic_frame(&schematic);
+
+        assert_eq!(frame.plain_text(), "467..114..\n...*......");
+        assert_eq!(frame.rows[0][0].style, Style::color(Color::Green).bold());
+        assert_eq!(frame.rows[0][5].style, Style::color(Color::Red));
+        assert_eq!(frame.rows[0][3].style, Style::color(Color::Gray));
+        assert_eq!(frame.rows[1][3].style, Style::color(Color::Yellow).bold());
+    }
+}
@@ -1,22 +1,28 @@
 use day4::scratchcards;
+use runner::{Input, Runner};
 
-fn parse_cards_file(data: &str) -> Vec<scratchcards::ScratchCard> {
-    let mut cards: Vec<scratchcards::ScratchCard> = Vec::new();
+use std::process;
 
-    for line in data.lines() {
-        cards.push(scratchcards::ScratchCard::from_card_line(&line));
-    }
-    cards
+fn parse_cards_file(input: &mut Input) -> impl Iterator<Item = scratchcards::ScratchCard> + '_ {
+    input.lines().map(|line| {
+        let line = line.expect("Could not read puzzle input");
+        scratchcards::ScratchCard::from_card_line(&line)
+    })
 }
 
 fn main() {
     println!("Part 1!");
-    let data = include_str!("../data.txt");
-    let scratchcards = parse_cards_file(data);
+    let runner = Runner::from_args();
+    let mut input = runner.open_input(env!("CARGO_MANIFEST_DIR"));
+    let mut scratchcards = parse_cards_file(&mut input);
     let total_points = scratchcards
-        .iter()
-        .map(|card| card.get_points())
-        .fold(0, |sum, points| sum + points);
+        .try_fold(0u64, |total, card| {
+            total.checked_add(card.get_points() as u64)
+        })
+        .unwrap_or_else(|| {
+            eprintln!("The total points don't fit in a u64");
+            process::exit(1);
+        });
 
     println!("Total points: {}", total_points);
 }
@@ -1,25 +1,27 @@
 use day4::scratchcards;
+use runner::{Input, Runner};
 
-fn parse_cards_file(data: &str) -> Vec<scratchcards::ScratchCard> {
-    let mut cards: Vec<scratchcards::ScratchCard> = Vec::new();
+use std::process;
 
-    for line in data.lines() {
-        cards.push(scratchcards::ScratchCard::from_card_line(&line));
-    }
-    cards
+fn parse_cards_file(input: &mut Input) -> impl Iterator<Item = scratchcards::ScratchCard> + '_ {
+    input.lines().map(|line| {
+        let line = line.expect("Could not read puzzle input");
+        scratchcards::ScratchCard::from_card_line(&line)
+    })
 }
 
 fn main() {
     println!("Part 2!");
-    let data = include_str!("../data.txt");
-    let cards = parse_cards_file(data);
-    let mut counts = vec![1u32; cards.len()];
+    let runner = Runner::from_args();
+    let mut input = runner.open_input(env!("CARGO_MANIFEST_DIR"));
+    let mut copies = scratchcards::CopyCounter::new();
 
-    for i in 0..cards.len() {
-        for j in 0..(cards[i].get_matches() as usize) {
-            counts[(i + j + 1) as usize] += counts[i];
-        }
+    for card in parse_cards_file(&mut input) {
+        copies.add_card(&card).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            process::exit(1);
+        });
     }
 
-    println!("Total points: {}", counts.iter().sum::<u32>());
+    println!("Total points: {}", copies.total);
 }
@@ -1,5 +1,6 @@
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
 
 #[derive(Debug)]
 pub struct ScratchCard {
@@ -19,9 +20,8 @@ impl ScratchCard {
 
 	pub fn get_matches(&self) -> u32 {
 		let intersection = self.winning_numbers.intersection(&self.own_numbers);
-		let intersection_count = intersection.count() as u32;
 
-		intersection_count
+		intersection.count() as u32
 	}
 
 	pub fn get_points(&self) -> u32 {
@@ -30,12 +30,61 @@ impl ScratchCard {
 		if matches == 0 {
 			return 0;
 		}
-		let power = 2u32.pow(matches - 1);
-		power
+		2u32.pow(matches - 1)
 	}
 
 	pub fn from_card_line(line: &str) -> ScratchCard {
-		return scratchcard_parser::parse_scratchcard_line(line);
+		scratchcard_parser::parse_scratchcard_line(line)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyOverflow {
+	pub card: u32,
+}
+
+impl fmt::Display for CopyOverflow {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "copies won up to card {} don't fit in a u64", self.card)
+	}
+}
+
+impl std::error::Error for CopyOverflow {}
+
+// Counts card copies while the cards are read one by one: `pending` holds the copies
+// already won of the cards that come next, so no card has to be kept around.
+#[derive(Debug, Default)]
+pub struct CopyCounter {
+	pending: VecDeque<u64>,
+	pub total: u64,
+}
+
+impl CopyCounter {
+	pub fn new() -> CopyCounter {
+		CopyCounter::default()
+	}
+
+	// The copies grow exponentially with long runs of winning cards, so every sum is
+	// checked. After an overflow the counter is left partially updated.
+	pub fn add_card(&mut self, card: &ScratchCard) -> Result<u64, CopyOverflow> {
+		let overflow = CopyOverflow { card: card.id };
+		let copies = self
+			.pending
+			.pop_front()
+			.unwrap_or(0)
+			.checked_add(1)
+			.ok_or(overflow.clone())?;
+		let matches = card.get_matches() as usize;
+
+		if self.pending.len() < matches {
+			self.pending.resize(matches, 0);
+		}
+		for won in self.pending.iter_mut().take(matches) {
+			*won = won.checked_add(copies).ok_or(overflow.clone())?;
+		}
+
+		self.total = self.total.checked_add(copies).ok_or(overflow)?;
+		Ok(copies)
 	}
 }
 
@@ -63,4 +112,58 @@ mod scratchcard_parser {
 
 		scratchcard
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const EXAMPLE: &str = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
+
+	#[test]
+	fn test_copy_counter() {
+		let mut copies = CopyCounter::new();
+		let counts: Vec<u64> = EXAMPLE
+			.lines()
+			.map(|line| copies.add_card(&ScratchCard::from_card_line(line)).unwrap())
+			.collect();
+
+		assert_eq!(counts, vec![1, 2, 4, 8, 14, 1]);
+		assert_eq!(copies.total, 30);
+	}
+
+	#[test]
+	fn test_copy_counter_overflow() {
+		// every card wins the next two, so the copies grow like the Fibonacci numbers
+		let mut copies = CopyCounter::new();
+		let result = (1..=200)
+			.map(|id| format!("Card {}: 1 2 | 1 2", id))
+			.try_for_each(|line| copies.add_card(&ScratchCard::from_card_line(&line)).map(|_| ()));
+
+		assert!(matches!(result, Err(CopyOverflow { card }) if card < 100));
+	}
+
+	#[test]
+	fn test_snapshot_match_counts() {
+		let summary = EXAMPLE
+			.lines()
+			.map(ScratchCard::from_card_line)
+			.map(|card| {
+				format!(
+					"Card {}: {} matches, {} points",
+					card.id,
+					card.get_matches(),
+					card.get_points()
+				)
+			})
+			.collect::<Vec<String>>()
+			.join("\n");
+
+		insta::assert_snapshot!(summary);
+	}
+}
@@ -0,0 +1,114 @@
+// Returns None when the boat can't even reach the distance, the parabola then never
+// crosses it.
+pub fn calc_boundary_charge_times<T: Into<f64> + From<f64>>(tx: T, dx: T) -> Option<(T, T)> {
+    let tx = tx.into();
+    let dx = dx.into();
+
+    let a = 1.0;
+    let b = -tx;
+    let c = dx;
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let sqrt_discriminant = discriminant.sqrt();
+    let root1 = (-b - sqrt_discriminant) / (2.0 * a);
+    let root2 = (-b + sqrt_discriminant) / (2.0 * a);
+
+    Some((root1.ceil().into(), root2.floor().into()))
+}
+
+// Holding the button for the whole race, or longer, leaves no time to move. The
+// distance is a u128 because the longest races go further than a u64 can hold.
+fn travelled_distance(time: u64, charge_time: u64) -> u128 {
+    charge_time as u128 * time.saturating_sub(charge_time) as u128
+}
+
+// Solves charge * (time - charge) > distance with the quadratic formula. When a root
+// is an integer the boat only ties the record there, so the boundaries move inwards.
+pub fn count_winning_charge_times(time: u64, distance: u64) -> u64 {
+    let Some((min_charge_time, max_charge_time)) =
+        calc_boundary_charge_times(time as f64, distance as f64)
+    else {
+        return 0;
+    };
+    let mut min_charge_time = min_charge_time as u64;
+    let mut max_charge_time = max_charge_time as u64;
+
+    if travelled_distance(time, min_charge_time) <= distance as u128 {
+        min_charge_time += 1;
+    }
+    if travelled_distance(time, max_charge_time) <= distance as u128 {
+        max_charge_time = max_charge_time.saturating_sub(1);
+    }
+
+    if max_charge_time < min_charge_time {
+        return 0;
+    }
+    max_charge_time - min_charge_time + 1
+}
+
+pub fn count_winning_charge_times_brute_force(time: u64, distance: u64) -> u64 {
+    (0..=time)
+        .filter(|&charge_time| travelled_distance(time, charge_time) > distance as u128)
+        .count() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calc_boundary_charge_times() {
+        assert_eq!(calc_boundary_charge_times(7.0, 9.0), Some((2.0, 5.0)));
+        assert_eq!(calc_boundary_charge_times(30.0, 200.0), Some((10.0, 20.0)));
+        assert_eq!(calc_boundary_charge_times(3.0, 10.0), None);
+    }
+
+    #[test]
+    fn test_count_winning_charge_times() {
+        assert_eq!(count_winning_charge_times(7, 9), 4);
+        assert_eq!(count_winning_charge_times(15, 40), 8);
+        assert_eq!(count_winning_charge_times(30, 200), 9);
+        assert_eq!(count_winning_charge_times(71530, 940200), 71503);
+    }
+
+    #[test]
+    fn test_count_winning_charge_times_brute_force() {
+        assert_eq!(count_winning_charge_times_brute_force(7, 9), 4);
+        assert_eq!(count_winning_charge_times_brute_force(15, 40), 8);
+        assert_eq!(count_winning_charge_times_brute_force(30, 200), 9);
+        assert_eq!(count_winning_charge_times_brute_force(71530, 940200), 71503);
+    }
+
+    #[test]
+    fn test_no_winning_charge_times() {
+        assert_eq!(count_winning_charge_times(4, 4), 0);
+        assert_eq!(count_winning_charge_times_brute_force(4, 4), 0);
+    }
+
+    #[test]
+    fn test_travelled_distance() {
+        assert_eq!(travelled_distance(7, 3), 12);
+        assert_eq!(travelled_distance(7, 7), 0);
+        assert_eq!(travelled_distance(7, 9), 0);
+        assert_eq!(
+            travelled_distance(u64::MAX, 1 << 63),
+            (1u128 << 63) * ((1u128 << 63) - 1)
+        );
+    }
+
+    #[test]
+    fn test_long_races() {
+        assert_eq!(
+            count_winning_charge_times(1 << 40, u64::MAX),
+            1_099_478_072_831
+        );
+    }
+
+    #[test]
+    fn test_unreachable_distance() {
+        assert_eq!(count_winning_charge_times(3, 10), 0);
+        assert_eq!(count_winning_charge_times_brute_force(3, 10), 0);
+    }
+}
@@ -1,14 +1,17 @@
 use std::collections::HashMap;
 
 use day7::camelcards::{CardValueMap, Game, Hand, HandType};
+use day7::visualization::ranking_frame;
+use runner::{Input, Runner};
 
 use itertools::Itertools;
 
-fn parse_game_data(data: &str) -> HashMap<String, u32> {
-    let input: Vec<&str> = data.lines().collect();
+use std::process;
 
+fn parse_game_data(input: &mut Input) -> Vec<(String, u32)> {
     input
-        .iter()
+        .lines()
+        .map(|entry| entry.expect("Could not read puzzle input"))
         .filter_map(|entry| {
             let parts: Vec<&str> = entry.split_whitespace().collect();
             if parts.len() == 2 {
@@ -24,7 +27,7 @@ fn parse_game_data(data: &str) -> HashMap<String, u32> {
 }
 
 fn get_hand_type(hand: &Hand, _map: &CardValueMap) -> HandType {
-    let counts = hand.original.chars().into_iter().counts();
+    let counts = hand.original.chars().counts();
     let fingerprints = counts.values().sorted().join("");
 
     match fingerprints.as_str() {
@@ -39,17 +42,19 @@ fn get_hand_type(hand: &Hand, _map: &CardValueMap) -> HandType {
     }
 }
 
-fn get_total_winnings(game: &Game) -> u32 {
-    let ordered_hands = game.get_sorted_hands();
-    ordered_hands.iter().enumerate().fold(0, |acc, (i, hand)| {
-        let winnings = *game.get_winning_for_hand(hand).unwrap();
-        let multiplier = i + 1;
-        acc + winnings * multiplier as u32
-    })
+// None when the total doesn't fit in a u64.
+fn get_total_winnings(game: &Game) -> Option<u64> {
+    game.get_ranked_bids()
+        .iter()
+        .enumerate()
+        .try_fold(0u64, |acc, (i, (_, bid))| {
+            acc.checked_add((*bid as u64).checked_mul(i as u64 + 1)?)
+        })
 }
 
 pub fn main() {
     println!("Part 1!");
+    let mut runner = Runner::from_args();
 
     let card_map: HashMap<char, u8> = HashMap::from([
         ('2', 2),
@@ -67,11 +72,16 @@ pub fn main() {
         ('A', 14),
     ]);
 
-    let data: &'static str = include_str!("../data.txt");
-    let parsed_input = parse_game_data(data);
+    let mut input = runner.open_input(env!("CARGO_MANIFEST_DIR"));
+    let parsed_input = parse_game_data(&mut input);
     let game = Game::new(parsed_input, card_map.clone(), get_hand_type);
 
-    let winnings = get_total_winnings(&game);
+    runner.visualize("ranking", || ranking_frame(&game));
+
+    let winnings = get_total_winnings(&game).unwrap_or_else(|| {
+        eprintln!("The total winnings don't fit in a u64");
+        process::exit(1);
+    });
     println!("Result: {}", winnings);
 }
 
@@ -79,15 +89,14 @@ pub fn main() {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_total_winnings() {
-        let input = HashMap::from([
+    fn example_game() -> Game {
+        let input = vec![
             ("32T3K".to_string(), 765),
             ("T55J5".to_string(), 684),
             ("KK677".to_string(), 28),
             ("KTJJT".to_string(), 220),
             ("QQQJA".to_string(), 483),
-        ]);
+        ];
 
         let card_map: HashMap<char, u8> = HashMap::from([
             ('2', 2),
@@ -105,7 +114,28 @@ mod tests {
             ('A', 14),
         ]);
 
-        let game = Game::new(input, card_map.clone(), get_hand_type);
-        assert_eq!(get_total_winnings(&game), 6440);
+        Game::new(input, card_map.clone(), get_hand_type)
+    }
+
+    #[test]
+    fn test_total_winnings() {
+        let game = example_game();
+        assert_eq!(get_total_winnings(&game), Some(6440));
+    }
+
+    #[test]
+    fn test_snapshot_sorted_hands() {
+        let game = example_game();
+        let ranking = game
+            .get_sorted_hands()
+            .iter()
+            .enumerate()
+            .map(|(i, hand)| {
+                let hand_type = get_hand_type(hand, &game.card_map);
+                format!("{}: {} {:?}", i + 1, hand.original, hand_type)
+            })
+            .join("\n");
+
+        insta::assert_snapshot!(ranking);
     }
 }
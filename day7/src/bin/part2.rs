@@ -1,14 +1,17 @@
 use std::collections::HashMap;
 
 use day7::camelcards::{CardValueMap, Game, Hand, HandType};
+use day7::visualization::ranking_frame;
+use runner::{Input, Runner};
 
 use itertools::Itertools;
 
-fn parse_game_data(data: &str) -> HashMap<String, u32> {
-    let input: Vec<&str> = data.lines().collect();
+use std::process;
 
+fn parse_game_data(input: &mut Input) -> Vec<(String, u32)> {
     input
-        .iter()
+        .lines()
+        .map(|entry| entry.expect("Could not read puzzle input"))
         .filter_map(|entry| {
             let parts: Vec<&str> = entry.split_whitespace().collect();
             if parts.len() == 2 {
@@ -24,7 +27,7 @@ fn parse_game_data(data: &str) -> HashMap<String, u32> {
 }
 
 fn get_hand_type(hand: &Hand, map: &CardValueMap) -> HandType {
-    let mut card_counts = hand.original.chars().into_iter().counts();
+    let mut card_counts = hand.original.chars().counts();
     let num_jokers = *card_counts.get(&'J').unwrap_or(&0);
 
     // dbg!(&card_counts);
@@ -34,16 +37,17 @@ fn get_hand_type(hand: &Hand, map: &CardValueMap) -> HandType {
     }
 
     if num_jokers > 0 {
-        // get the highest card value that is not a joker
-        let other_cards = card_counts
-            .keys()
-            .filter(|&c| *c != 'J')
-            .collect::<Vec<&char>>();
-        let highest_card = other_cards.iter().max_by_key(|&&c| map.get(&c)).unwrap();
-
-        // add the number of jokers to the count of the highest card
-        let new_count = card_counts.get(highest_card).unwrap() + num_jokers;
-        card_counts.insert(**highest_card, new_count);
+        // jokers always do best by joining the most frequent other card, the value of
+        // that card only breaks ties
+        let best_card = *card_counts
+            .iter()
+            .filter(|&(&c, _)| c != 'J')
+            .max_by_key(|&(c, &count)| (count, map.get(c)))
+            .unwrap()
+            .0;
+
+        // add the number of jokers to the count of the most frequent card
+        *card_counts.get_mut(&best_card).unwrap() += num_jokers;
         card_counts.remove(&'J');
 
         // dbg!(&card_counts);
@@ -63,31 +67,21 @@ fn get_hand_type(hand: &Hand, map: &CardValueMap) -> HandType {
     }
 }
 
-fn get_total_winnings(game: &Game) -> u32 {
-    let ordered_hands = game.get_sorted_hands();
-
-    ordered_hands
+// None when the total doesn't fit in a u64.
+fn get_total_winnings(game: &Game) -> Option<u64> {
+    game.get_ranked_bids()
         .iter()
         .enumerate()
-        .inspect(|(i, hand)| {
-            let winnings = *game.get_winning_for_hand(hand).unwrap();
-            println!(
-                "{}: {:?} : {} -> {}",
-                i + 1,
-                hand.original,
-                winnings,
-                winnings * (i + 1) as u32
-            );
-        })
-        .fold(0, |acc, (i, hand)| {
-            let winnings = *game.get_winning_for_hand(hand).unwrap();
-            let multiplier = i + 1;
-            acc + winnings * multiplier as u32
+        .try_fold(0u64, |acc, (i, (hand, bid))| {
+            let winnings = (*bid as u64).checked_mul(i as u64 + 1)?;
+            println!("{}: {:?} : {} -> {}", i + 1, hand.original, bid, winnings);
+            acc.checked_add(winnings)
         })
 }
 
 pub fn main() {
     println!("Part 2!");
+    let mut runner = Runner::from_args();
 
     let card_map: HashMap<char, u8> = HashMap::from([
         ('J', 1),
@@ -105,11 +99,16 @@ pub fn main() {
         ('A', 14),
     ]);
 
-    let data: &'static str = include_str!("../data.txt");
-    let parsed_input = parse_game_data(data);
+    let mut input = runner.open_input(env!("CARGO_MANIFEST_DIR"));
+    let parsed_input = parse_game_data(&mut input);
     let game = Game::new(parsed_input, card_map.clone(), get_hand_type);
 
-    let winnings = get_total_winnings(&game);
+    runner.visualize("ranking", || ranking_frame(&game));
+
+    let winnings = get_total_winnings(&game).unwrap_or_else(|| {
+        eprintln!("The total winnings don't fit in a u64");
+        process::exit(1);
+    });
     println!("Result: {}", winnings);
 }
 
@@ -117,15 +116,14 @@ pub fn main() {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_total_winnings() {
-        let input = HashMap::from([
+    fn example_game() -> Game {
+        let input = vec![
             ("32T3K".to_string(), 765),
             ("T55J5".to_string(), 684),
             ("KK677".to_string(), 28),
             ("KTJJT".to_string(), 220),
             ("QQQJA".to_string(), 483),
-        ]);
+        ];
 
         let card_map: HashMap<char, u8> = HashMap::from([
             ('J', 1),
@@ -143,7 +141,39 @@ mod tests {
             ('A', 14),
         ]);
 
-        let game = Game::new(input, card_map.clone(), get_hand_type);
-        assert_eq!(get_total_winnings(&game), 5905);
+        Game::new(input, card_map.clone(), get_hand_type)
+    }
+
+    #[test]
+    fn test_jokers_join_most_frequent_card() {
+        let game = example_game();
+        let hand_type = |hand| get_hand_type(&Hand::from_str(hand, &game.card_map), &game.card_map);
+
+        // the joker joins the kings, not the higher but single ace
+        assert_eq!(hand_type("AKKQJ"), HandType::ThreeOfAKind);
+        // between two pairs it makes a full house
+        assert_eq!(hand_type("2233J"), HandType::FullHouse);
+    }
+
+    #[test]
+    fn test_total_winnings() {
+        let game = example_game();
+        assert_eq!(get_total_winnings(&game), Some(5905));
+    }
+
+    #[test]
+    fn test_snapshot_sorted_hands() {
+        let game = example_game();
+        let ranking = game
+            .get_sorted_hands()
+            .iter()
+            .enumerate()
+            .map(|(i, hand)| {
+                let hand_type = get_hand_type(hand, &game.card_map);
+                format!("{}: {} {:?}", i + 1, hand.original, hand_type)
+            })
+            .join("\n");
+
+        insta::assert_snapshot!(ranking);
     }
 }
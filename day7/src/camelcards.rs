@@ -30,15 +30,17 @@ pub enum HandType {
     FiveOfAKind,
 }
 
+// Hands with their bids in input order. A hand can be dealt more than once, every
+// copy keeps its own bid.
 pub struct Game {
-    pub hands: HashMap<String, u32>,
+    pub hands: Vec<(String, u32)>,
     pub card_map: CardValueMap,
     pub calc_hand_strength: fn(&Hand, &CardValueMap) -> HandType,
 }
 
 impl Game {
     pub fn new(
-        hands: HashMap<String, u32>,
+        hands: Vec<(String, u32)>,
         card_map: CardValueMap,
         calc_hand_strength: fn(&Hand, &CardValueMap) -> HandType,
     ) -> Game {
@@ -48,18 +50,27 @@ impl Game {
             calc_hand_strength,
         }
     }
+    // The bid of the first copy of the hand.
     pub fn get_winning_for_hand(&self, hand: &Hand) -> Option<&u32> {
-        let str_hand = hand.original.clone();
-        self.hands.get(&str_hand)
+        self.hands
+            .iter()
+            .find(|(original, _)| *original == hand.original)
+            .map(|(_, bid)| bid)
     }
 
     pub fn get_sorted_hands(&self) -> Vec<Hand> {
-        self.hands
-            .keys()
-            .cloned()
+        self.get_ranked_bids()
             .into_iter()
-            .map(|hand| Hand::from_str(&*hand, &self.card_map))
-            .sorted_by(|h1, h2| {
+            .map(|(hand, _)| hand)
+            .collect()
+    }
+
+    // Every hand with its bid, weakest first. Copies of a hand stay in input order.
+    pub fn get_ranked_bids(&self) -> Vec<(Hand, u32)> {
+        self.hands
+            .iter()
+            .map(|(hand, bid)| (Hand::from_str(hand, &self.card_map), *bid))
+            .sorted_by(|(h1, _), (h2, _)| {
                 let h1_type = (self.calc_hand_strength)(h1, &self.card_map);
                 let h2_type = (self.calc_hand_strength)(h2, &self.card_map);
                 let h1_vals = h1.values.clone();
@@ -69,7 +80,7 @@ impl Game {
                     return h1_vals.cmp(&h2_vals);
                 }
 
-                return h1_type.cmp(&h2_type);
+                h1_type.cmp(&h2_type)
             })
             .collect()
     }
@@ -91,14 +102,11 @@ mod tests {
 
     #[test]
     fn test_get_winning_for_hand() {
-        let hands: HashMap<String, u32> = [("ABC".to_string(), 1), ("DEF".to_string(), 2)]
-            .iter()
-            .cloned()
-            .collect();
+        let hands = vec![("ABC".to_string(), 1), ("DEF".to_string(), 2)];
 
         let card_map: CardValueMap = [('A', 1), ('B', 2), ('C', 3)].iter().cloned().collect();
 
-        let game = Game::new(hands, card_map, |_, _| HandType::HighCard);
+        let game = Game::new(hands, card_map.clone(), |_, _| HandType::HighCard);
 
         let hand = Hand::from_str("ABC", &card_map);
         let winning = game.get_winning_for_hand(&hand);
@@ -108,17 +116,27 @@ mod tests {
 
     #[test]
     fn test_get_sorted_hands() {
-        let hands: HashMap<String, u32> = [
+        let hands = vec![
+            ("GHI".to_string(), 3),
             ("ABC".to_string(), 1),
             ("DEF".to_string(), 2),
-            ("GHI".to_string(), 3),
+        ];
+
+        let card_map: CardValueMap = [
+            ('A', 1),
+            ('B', 2),
+            ('C', 3),
+            ('D', 4),
+            ('E', 5),
+            ('F', 6),
+            ('G', 7),
+            ('H', 8),
+            ('I', 9),
         ]
         .iter()
         .cloned()
         .collect();
 
-        let card_map: CardValueMap = [('A', 1), ('B', 2), ('C', 3)].iter().cloned().collect();
-
         let game = Game::new(hands, card_map, |_, _| HandType::HighCard);
 
         let sorted_hands = game.get_sorted_hands();
@@ -128,4 +146,30 @@ mod tests {
         assert_eq!(sorted_hands[1].original, "DEF");
         assert_eq!(sorted_hands[2].original, "GHI");
     }
+
+    #[test]
+    fn test_repeated_hands() {
+        let hands = vec![
+            ("ABC".to_string(), 5),
+            ("AAB".to_string(), 2),
+            ("ABC".to_string(), 7),
+        ];
+        let card_map: CardValueMap = [('A', 1), ('B', 2), ('C', 3)].iter().cloned().collect();
+        let game = Game::new(hands, card_map, |_, _| HandType::HighCard);
+
+        let ranked: Vec<(String, u32)> = game
+            .get_ranked_bids()
+            .into_iter()
+            .map(|(hand, bid)| (hand.original, bid))
+            .collect();
+
+        assert_eq!(
+            ranked,
+            vec![
+                ("AAB".to_string(), 2),
+                ("ABC".to_string(), 5),
+                ("ABC".to_string(), 7)
+            ]
+        );
+    }
 }
@@ -1 +1,2 @@
 pub mod camelcards;
+pub mod visualization;
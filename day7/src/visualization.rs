@@ -0,0 +1,74 @@
+use visualize::{Color, Frame, Style};
+
+use crate::camelcards::{Game, HandType};
+
+fn hand_type_color(hand_type: &HandType) -> Color {
+    match hand_type {
+        HandType::HighCard => Color::Gray,
+        HandType::OnePair => Color::Default,
+        HandType::TwoPair => Color::Cyan,
+        HandType::ThreeOfAKind => Color::Blue,
+        HandType::FullHouse => Color::Green,
+        HandType::FourOfAKind => Color::Yellow,
+        HandType::FiveOfAKind => Color::Red,
+    }
+}
+
+// One row per hand in ranking order (weakest first), colored by hand type.
+pub fn ranking_frame(game: &Game) -> Frame {
+    let mut frame = Frame::new("Day 7: camel cards ranking");
+    frame.push_line(
+        &format!(
+            "{:>5}  {:<5}  {:<12}  {:>5}  {:>9}",
+            "rank", "hand", "type", "bid", "winnings"
+        ),
+        Style::plain().bold(),
+    );
+
+    for (i, (hand, bid)) in game.get_ranked_bids().iter().enumerate() {
+        let hand_type = (game.calc_hand_strength)(hand, &game.card_map);
+        let rank = i as u64 + 1;
+        let style = Style::color(hand_type_color(&hand_type));
+
+        frame.push_spans(&[
+            (&format!("{:>5}  ", rank), Style::plain()),
+            (&format!("{:<5}", hand.original), style.bold()),
+            (&format!("  {:<12}", format!("{:?}", hand_type)), style),
+            (
+                &format!("  {:>5}  {:>9}", bid, *bid as u64 * rank),
+                Style::plain(),
+            ),
+        ]);
+    }
+
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camelcards::CardValueMap;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_ranking_frame() {
+        let hands = vec![("AAB".to_string(), 10), ("ABC".to_string(), 3)];
+        let card_map: CardValueMap = HashMap::from([('A', 1), ('B', 2), ('C', 3)]);
+        let game = Game::new(hands, card_map, |hand, _| {
+            if hand.original == "AAB" {
+                HandType::OnePair
+            } else {
+                HandType::HighCard
+            }
+        });
+
+        let frame = ranking_frame(&game);
+        let text = frame.plain_text();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], "    1  ABC    HighCard          3          3");
+        assert_eq!(lines[2], "    2  AAB    OnePair          10         20");
+        assert_eq!(frame.rows[1][7].style, Style::color(Color::Gray).bold());
+    }
+}
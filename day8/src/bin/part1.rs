@@ -3,7 +3,9 @@
 // the rules are in no particular order
 // the rules are not necessarily connected to the path or each other
 use fancy_regex::Regex;
+use runner::Runner;
 use std::collections::HashMap;
+use visualize::{Color, Frame, Style};
 
 pub fn parse_instructions(instructions: &str) -> (&str, &str) {
     let mut input_iter = instructions.split("\n\n");
@@ -60,12 +62,82 @@ pub fn find_path<'a>(
     path
 }
 
+// same walk as find_path, but keeps every node visited (start and end included)
+pub fn find_route<'a>(
+    start: &'a str,
+    end: &'a str,
+    moves_list: &str,
+    adjacency_list: &HashMap<&'a str, (&'a str, &'a str)>,
+) -> Vec<&'a str> {
+    let mut route = vec![start];
+    let mut current_node = start;
+
+    for direction in moves_list.chars().cycle() {
+        if current_node == end {
+            break;
+        }
+        let (left, right) = adjacency_list.get(current_node).unwrap();
+
+        current_node = match direction {
+            'L' => left,
+            _ => right,
+        };
+        route.push(current_node);
+    }
+
+    route
+}
+
+const HOPS_PER_ROW: usize = 8;
+
+pub fn route_frame(moves_list: &str, route: &[&str]) -> Frame {
+    let mut frame = Frame::new(&format!("Day 8: route in {} steps", route.len() - 1));
+    let directions: Vec<char> = moves_list.chars().cycle().take(route.len() - 1).collect();
+
+    for (row, nodes) in route.chunks(HOPS_PER_ROW).enumerate() {
+        let mut spans: Vec<(String, Style)> = Vec::new();
+
+        for (i, node) in nodes.iter().enumerate() {
+            let step = row * HOPS_PER_ROW + i;
+            let node_style = if step == 0 {
+                Style::color(Color::Green).bold()
+            } else if step == route.len() - 1 {
+                Style::color(Color::Red).bold()
+            } else {
+                Style::plain()
+            };
+
+            if i > 0 || step > 0 {
+                let direction = directions[step - 1];
+                let direction_color = if direction == 'L' { Color::Cyan } else { Color::Magenta };
+                spans.push((format!(" -{}-> ", direction), Style::color(direction_color)));
+            }
+            spans.push((node.to_string(), node_style));
+        }
+
+        let spans: Vec<(&str, Style)> = spans
+            .iter()
+            .map(|(text, style)| (text.as_str(), *style))
+            .collect();
+        frame.push_spans(&spans);
+    }
+
+    frame
+}
+
 pub fn main() {
     println!("Part 1!");
-    let data = include_str!("../data.txt");
+    let mut runner = Runner::from_args();
+    let data = runner.load_input(env!("CARGO_MANIFEST_DIR"));
 
-    let (moves_list, nodes_list) = parse_instructions(data);
+    let (moves_list, nodes_list) = parse_instructions(&data);
     let adjacency_list = build_adjacency_list(nodes_list);
+
+    runner.visualize("route", || {
+        let route = find_route("AAA", "ZZZ", moves_list, &adjacency_list);
+        route_frame(moves_list, &route)
+    });
+
     let path = find_path("AAA", "ZZZ", moves_list, adjacency_list);
 
     println!("Path: {:?}", path.join(""));
@@ -155,4 +227,67 @@ ZZZ = (ZZZ, ZZZ)
         assert_eq!(path.len(), 6);
         assert_eq!(path, vec!["L", "L", "R", "L", "L", "R"]);
     }
+
+    #[test]
+    fn test_find_route() {
+        let input1 = r#"LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+"#;
+
+        let (moves_list, nodes_list) = parse_instructions(input1);
+        let adjacency_list = build_adjacency_list(nodes_list);
+
+        let route = find_route("AAA", "ZZZ", moves_list, &adjacency_list);
+
+        assert_eq!(route, vec!["AAA", "BBB", "AAA", "BBB", "AAA", "BBB", "ZZZ"]);
+    }
+
+    #[test]
+    fn test_snapshot_traced_routes() {
+        let input1 = r#"RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+"#;
+        let input2 = r#"LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+"#;
+
+        let traced_routes = [input1, input2]
+            .iter()
+            .map(|input| {
+                let (moves_list, nodes_list) = parse_instructions(input);
+                let adjacency_list = build_adjacency_list(nodes_list);
+                find_route("AAA", "ZZZ", moves_list, &adjacency_list).join(" -> ")
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        insta::assert_snapshot!(traced_routes);
+    }
+
+    #[test]
+    fn test_route_frame() {
+        let route = vec!["AAA", "BBB", "AAA", "BBB", "AAA", "BBB", "AAA", "BBB", "ZZZ"];
+        let frame = route_frame("LLR", &route);
+
+        assert_eq!(frame.title, "Day 8: route in 8 steps");
+        assert_eq!(
+            frame.plain_text(),
+            "AAA -L-> BBB -L-> AAA -R-> BBB -L-> AAA -L-> BBB -R-> AAA -L-> BBB\n -L-> ZZZ"
+        );
+        assert_eq!(frame.rows[0][0].style, Style::color(Color::Green).bold());
+        assert_eq!(frame.rows[1][6].style, Style::color(Color::Red).bold());
+    }
 }
\ No newline at end of file
@@ -6,7 +6,7 @@ pub fn main() {
 mod tests {
     #[test]
     fn test_example_1() {
-        let input1 = r#"
+        let _input1 = r#"
         RL
 
         AAA = (BBB, CCC)
@@ -22,7 +22,7 @@ mod tests {
 
     #[test]
     fn test_example_2() {
-        let input2 = r#"
+        let _input2 = r#"
         LLR
 
         AAA = (BBB, BBB)
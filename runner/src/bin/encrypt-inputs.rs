@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::Parser;
+use runner::input_store::{self, InputKey, KEY_VAR};
+
+/// Encrypts every dayN/src/data.txt into dayN/src/data.txt.enc using the key in AOC_INPUT_KEY
+#[derive(Parser, Debug)]
+struct Args {
+    /// Workspace root containing the dayN directories
+    #[arg(default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/.."))]
+    root: PathBuf,
+
+    /// Delete each data.txt once its encrypted copy has been verified
+    #[arg(long)]
+    remove_plaintext: bool,
+}
+
+fn day_source_dirs(root: &Path) -> Vec<PathBuf> {
+    let entries = fs::read_dir(root).unwrap_or_else(|err| {
+        eprintln!("Could not read {}: {}", root.display(), err);
+        process::exit(1);
+    });
+
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("day"))
+        .map(|entry| entry.path().join("src"))
+        .filter(|dir| dir.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn main() {
+    let args = Args::parse();
+    let key = InputKey::from_env().unwrap_or_else(|| {
+        eprintln!("Set {} to the key used to encrypt the inputs", KEY_VAR);
+        process::exit(1);
+    });
+
+    for dir in day_source_dirs(&args.root) {
+        match input_store::migrate(&dir, &key, args.remove_plaintext) {
+            Ok(Some(path)) => println!("Encrypted {}", path.display()),
+            Ok(None) => {}
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use clap::{Args, CommandFactory, FromArgMatches, Parser};
+
+// Flags shared by every day's part1/part2 binaries.
+#[derive(Parser, Debug, Default)]
+#[command(about = "Advent of Code solver")]
+pub struct Cli {
+    /// Read the puzzle input from this file instead of the stored one ("-" for stdin)
+    #[arg(long, value_name = "PATH")]
+    pub input: Option<PathBuf>,
+
+    /// Print visualizations of the solver to the terminal
+    #[arg(long)]
+    pub visualize: bool,
+
+    /// Write visualizations as numbered SVG files into this directory
+    #[arg(long, value_name = "DIR")]
+    pub visualize_dir: Option<PathBuf>,
+}
+
+// Parses the shared flags together with the extra ones a day's binary defines
+// in its own `#[derive(Args)]` struct.
+pub fn parse_with<A, I, T>(args: I) -> Result<(Cli, A), clap::Error>
+where
+    A: Args,
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let matches = A::augment_args(Cli::command()).try_get_matches_from(args)?;
+    Ok((
+        Cli::from_arg_matches(&matches)?,
+        A::from_arg_matches(&matches)?,
+    ))
+}
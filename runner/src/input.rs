@@ -0,0 +1,279 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufRead, Read};
+use std::path::Path;
+
+use memmap2::Mmap;
+
+// A puzzle input that can be walked line by line without copying it into one big
+// string first. Files are memory-mapped, so the OS pages them in and out as needed.
+pub enum Input {
+    Memory(String),
+    Mapped(Mmap),
+    Reader(Box<dyn BufRead>),
+}
+
+impl Input {
+    pub fn from_string(data: String) -> Input {
+        Input::Memory(data)
+    }
+
+    pub fn open(path: &Path) -> io::Result<Input> {
+        let file = File::open(path)?;
+
+        // empty files can't be mapped on every platform
+        if file.metadata()?.len() == 0 {
+            return Ok(Input::Memory(String::new()));
+        }
+
+        // Safety: puzzle inputs are not expected to change while a solver is reading them.
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(Input::Mapped(mmap))
+    }
+
+    pub fn from_reader<R: BufRead + 'static>(reader: R) -> Input {
+        Input::Reader(Box::new(reader))
+    }
+
+    // Cuts the input into about `count` pieces of whole lines for solvers that spread
+    // the lines over threads. Strings and mapped files are split in place, a stream
+    // has to be read to the end first.
+    pub fn line_chunks(&mut self, count: usize) -> io::Result<Vec<&str>> {
+        if let Input::Reader(reader) = self {
+            let mut data = String::new();
+            reader.read_to_string(&mut data)?;
+            *self = Input::Memory(data);
+        }
+
+        let text = match self {
+            Input::Memory(data) => data.as_str(),
+            Input::Mapped(mmap) => std::str::from_utf8(&mmap[..])
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+            Input::Reader(_) => unreachable!("Streams were read into memory above"),
+        };
+
+        Ok(split_lines(text, count))
+    }
+
+    pub fn lines(&mut self) -> Lines<'_> {
+        match self {
+            Input::Memory(data) => Lines::Memory(data.lines()),
+            Input::Mapped(mmap) => Lines::Mapped(&mmap[..]),
+            Input::Reader(reader) => Lines::Reader(reader.as_mut(), String::new()),
+        }
+    }
+}
+
+// Cuts a text into about `count` pieces of similar size. Every piece but the last
+// ends right after a newline, so each one holds whole lines.
+pub fn split_lines(text: &str, count: usize) -> Vec<&str> {
+    let size = text.len().div_ceil(count.max(1)).max(1);
+    let mut chunks = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        // the cut is made after a newline, which never falls inside a character
+        let cut = match rest
+            .as_bytes()
+            .get(size..)
+            .and_then(|tail| tail.iter().position(|&b| b == b'\n'))
+        {
+            Some(newline) => size + newline + 1,
+            None => rest.len(),
+        };
+        let (chunk, tail) = rest.split_at(cut);
+        chunks.push(chunk);
+        rest = tail;
+    }
+
+    chunks
+}
+
+pub enum Lines<'a> {
+    Memory(std::str::Lines<'a>),
+    Mapped(&'a [u8]),
+    Reader(&'a mut dyn BufRead, String),
+}
+
+fn trim_line_ending(line: &[u8]) -> &[u8] {
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = io::Result<Cow<'a, str>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Lines::Memory(lines) => lines.next().map(|line| Ok(Cow::Borrowed(line))),
+            Lines::Mapped(rest) => {
+                if rest.is_empty() {
+                    return None;
+                }
+
+                let bytes: &'a [u8] = rest;
+                let (line, remaining) = match bytes.iter().position(|&b| b == b'\n') {
+                    Some(end) => (&bytes[..end], &bytes[end + 1..]),
+                    None => (bytes, &bytes[bytes.len()..]),
+                };
+                *rest = remaining;
+
+                Some(
+                    std::str::from_utf8(trim_line_ending(line))
+                        .map(Cow::Borrowed)
+                        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+                )
+            }
+            Lines::Reader(reader, buffer) => {
+                buffer.clear();
+                match reader.read_line(buffer) {
+                    Ok(0) => None,
+                    Ok(_) => {
+                        let line = buffer.strip_suffix('\n').unwrap_or(buffer);
+                        let line = line.strip_suffix('\r').unwrap_or(line);
+                        Some(Ok(Cow::Owned(line.to_string())))
+                    }
+                    Err(err) => Some(Err(err)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Cursor;
+
+    fn collect_lines(input: &mut Input) -> Vec<String> {
+        input
+            .lines()
+            .map(|line| line.unwrap().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_memory_lines() {
+        let mut input = Input::from_string("1abc2\r\npqr3stu8vwx\n\na1b2c3d4e5f".to_string());
+
+        assert_eq!(
+            collect_lines(&mut input),
+            vec!["1abc2", "pqr3stu8vwx", "", "a1b2c3d4e5f"]
+        );
+    }
+
+    #[test]
+    fn test_reader_lines() {
+        let mut input = Input::from_reader(Cursor::new("1abc2\r\npqr3stu8vwx\n\na1b2c3d4e5f"));
+
+        assert_eq!(
+            collect_lines(&mut input),
+            vec!["1abc2", "pqr3stu8vwx", "", "a1b2c3d4e5f"]
+        );
+    }
+
+    #[test]
+    fn test_mapped_lines() {
+        let path = std::env::temp_dir().join(format!("input-mapped-{}.txt", std::process::id()));
+        fs::write(&path, "1abc2\r\npqr3stu8vwx\n\na1b2c3d4e5f\n").unwrap();
+
+        let mut input = Input::open(&path).unwrap();
+        assert!(matches!(input, Input::Mapped(_)));
+        assert_eq!(
+            collect_lines(&mut input),
+            vec!["1abc2", "pqr3stu8vwx", "", "a1b2c3d4e5f"]
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_mapped_invalid_utf8() {
+        let path = std::env::temp_dir().join(format!("input-invalid-{}.txt", std::process::id()));
+        fs::write(&path, b"ok\n\xff\xfe\n").unwrap();
+
+        let mut input = Input::open(&path).unwrap();
+        let lines: Vec<io::Result<Cow<str>>> = input.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].as_ref().unwrap(), "ok");
+        assert_eq!(
+            lines[1].as_ref().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_line_chunks() {
+        let document = "1abc2\r\npqr3stu8vwx\n\na1b2c3d4e5f\ntreb7uchet\n";
+        let path = std::env::temp_dir().join(format!("input-chunks-{}.txt", std::process::id()));
+        fs::write(&path, document).unwrap();
+
+        let mut inputs = [
+            Input::from_string(document.to_string()),
+            Input::open(&path).unwrap(),
+            Input::from_reader(Cursor::new(document)),
+        ];
+        for input in inputs.iter_mut() {
+            for count in 1..8 {
+                let chunks = input.line_chunks(count).unwrap();
+                assert_eq!(chunks.concat(), document);
+                assert!(chunks.len() <= count);
+                assert!(chunks.iter().all(|chunk| chunk.ends_with('\n')));
+            }
+            assert_eq!(
+                input.line_chunks(2).unwrap(),
+                vec!["1abc2\r\npqr3stu8vwx\n\na1b2c3d4e5f\n", "treb7uchet\n"]
+            );
+        }
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_split_lines() {
+        let document = "one\ntwo\nthree\nfour\r\nfive";
+
+        for count in 1..8 {
+            let chunks = split_lines(document, count);
+            assert_eq!(chunks.concat(), document);
+            assert!(chunks[..chunks.len() - 1]
+                .iter()
+                .all(|chunk| chunk.ends_with('\n')));
+        }
+        assert_eq!(
+            split_lines(document, 2),
+            vec!["one\ntwo\nthree\n", "four\r\nfive"]
+        );
+        assert_eq!(split_lines("", 4), Vec::<&str>::new());
+        assert_eq!(split_lines("é\nü\n", 8), vec!["é\n", "ü\n"]);
+    }
+
+    #[test]
+    fn test_line_chunks_invalid_utf8() {
+        let path =
+            std::env::temp_dir().join(format!("input-chunks-invalid-{}.txt", std::process::id()));
+        fs::write(&path, b"ok\n\xff\xfe\n").unwrap();
+
+        let mut input = Input::open(&path).unwrap();
+        assert_eq!(
+            input.line_chunks(2).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_open_empty_file() {
+        let path = std::env::temp_dir().join(format!("input-empty-{}.txt", std::process::id()));
+        fs::write(&path, "").unwrap();
+
+        let mut input = Input::open(&path).unwrap();
+        assert_eq!(collect_lines(&mut input), Vec::<String>::new());
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,347 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use pbkdf2::pbkdf2_hmac;
+use sha2::Sha256;
+
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+pub const PLAIN_FILE: &str = "data.txt";
+pub const ENCRYPTED_FILE: &str = "data.txt.enc";
+
+pub const DEFAULT_ROUNDS: u32 = 600_000;
+// A corrupted or hostile header must not be able to stall a run for hours before the
+// key is even tried, so decryption refuses anything above this.
+pub const MAX_ROUNDS: u32 = 10_000_000;
+
+const ROUNDS_LEN: usize = 4;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = ROUNDS_LEN + SALT_LEN + NONCE_LEN;
+
+#[derive(Debug)]
+pub enum InputError {
+    Io(PathBuf, io::Error),
+    MissingInput(PathBuf),
+    MissingKey(PathBuf),
+    Corrupted(PathBuf),
+    WrongKey(PathBuf),
+    Mismatch(PathBuf),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Io(path, err) => write!(f, "could not access {}: {}", path.display(), err),
+            InputError::MissingInput(dir) => {
+                write!(
+                    f,
+                    "no {} or {} in {}, save your puzzle input as {} or pass it with --input",
+                    PLAIN_FILE,
+                    ENCRYPTED_FILE,
+                    dir.display(),
+                    dir.join(PLAIN_FILE).display()
+                )
+            }
+            InputError::MissingKey(path) => write!(
+                f,
+                "{} is encrypted, set {} to the input key to read it",
+                path.display(),
+                KEY_VAR
+            ),
+            InputError::Corrupted(path) => {
+                write!(f, "{} is not a valid encrypted input", path.display())
+            }
+            InputError::WrongKey(path) => {
+                write!(
+                    f,
+                    "could not decrypt {}, check the value of {}",
+                    path.display(),
+                    KEY_VAR
+                )
+            }
+            InputError::Mismatch(path) => write!(
+                f,
+                "{} does not decrypt back to the plain input, keeping the plain input",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+// The key is derived from a passphrase so it can live in a plain environment variable.
+// Every file gets its own random salt and the passphrase is stretched with PBKDF2, so
+// guessing it means paying for `rounds` hashes per guess and per file.
+#[derive(Clone)]
+pub struct InputKey {
+    passphrase: String,
+    rounds: u32,
+}
+
+impl InputKey {
+    pub fn from_passphrase(passphrase: &str) -> InputKey {
+        InputKey {
+            passphrase: passphrase.to_string(),
+            rounds: DEFAULT_ROUNDS,
+        }
+    }
+
+    // Only affects files encrypted with this key, decryption uses the rounds in the header.
+    pub fn with_rounds(self, rounds: u32) -> InputKey {
+        InputKey {
+            rounds: rounds.clamp(1, MAX_ROUNDS),
+            ..self
+        }
+    }
+
+    pub fn from_env() -> Option<InputKey> {
+        std::env::var(KEY_VAR)
+            .ok()
+            .filter(|passphrase| !passphrase.is_empty())
+            .map(|passphrase| InputKey::from_passphrase(&passphrase))
+    }
+
+    fn derive(&self, salt: &[u8], rounds: u32) -> Key {
+        let mut key = Key::default();
+        pbkdf2_hmac::<Sha256>(self.passphrase.as_bytes(), salt, rounds, key.as_mut_slice());
+        key
+    }
+}
+
+// Encrypted inputs are stored as base64 of `rounds || salt || nonce || ciphertext`, so
+// the files stay text and every encryption of the same input uses a fresh salt and nonce.
+// The rounds are a big-endian u32 so the work factor can be raised without breaking
+// files that were already encrypted.
+pub fn encrypt(key: &InputKey, plaintext: &str) -> String {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let cipher = ChaCha20Poly1305::new(&key.derive(&salt, key.rounds));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .expect("Encrypting in memory can't fail");
+
+    let mut payload = key.rounds.to_be_bytes().to_vec();
+    payload.extend(salt);
+    payload.extend(nonce);
+    payload.extend(ciphertext);
+
+    let mut encoded = STANDARD.encode(payload);
+    encoded.push('\n');
+    encoded
+}
+
+pub fn decrypt(key: &InputKey, encoded: &str, path: &Path) -> Result<String, InputError> {
+    let payload = STANDARD
+        .decode(encoded.trim())
+        .map_err(|_| InputError::Corrupted(path.to_path_buf()))?;
+
+    if payload.len() < HEADER_LEN {
+        return Err(InputError::Corrupted(path.to_path_buf()));
+    }
+
+    let (rounds, rest) = payload.split_at(ROUNDS_LEN);
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let rounds = u32::from_be_bytes(rounds.try_into().expect("Header has a fixed size"));
+    if rounds == 0 || rounds > MAX_ROUNDS {
+        return Err(InputError::Corrupted(path.to_path_buf()));
+    }
+
+    let cipher = ChaCha20Poly1305::new(&key.derive(salt, rounds));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| InputError::WrongKey(path.to_path_buf()))?;
+
+    String::from_utf8(plaintext).map_err(|_| InputError::Corrupted(path.to_path_buf()))
+}
+
+fn read(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|err| InputError::Io(path.to_path_buf(), err))
+}
+
+// Loads the puzzle input of a day from its `src` directory, decrypting `data.txt.enc`
+// when a key is available and falling back to a plain `data.txt` otherwise.
+pub fn load_with_key(src_dir: &Path, key: Option<&InputKey>) -> Result<String, InputError> {
+    let encrypted_path = src_dir.join(ENCRYPTED_FILE);
+    let plain_path = src_dir.join(PLAIN_FILE);
+
+    match (encrypted_path.exists(), key) {
+        (true, Some(key)) => decrypt(key, &read(&encrypted_path)?, &encrypted_path),
+        _ if plain_path.exists() => read(&plain_path),
+        (true, None) => Err(InputError::MissingKey(encrypted_path)),
+        (false, _) => Err(InputError::MissingInput(src_dir.to_path_buf())),
+    }
+}
+
+pub fn load(src_dir: &Path) -> Result<String, InputError> {
+    load_with_key(src_dir, InputKey::from_env().as_ref())
+}
+
+// Encrypts `data.txt` into `data.txt.enc` next to it. Returns the path written,
+// or None when the directory has no plain input to migrate.
+pub fn migrate(
+    src_dir: &Path,
+    key: &InputKey,
+    remove_plaintext: bool,
+) -> Result<Option<PathBuf>, InputError> {
+    let plain_path = src_dir.join(PLAIN_FILE);
+    if !plain_path.exists() {
+        return Ok(None);
+    }
+
+    let encrypted_path = src_dir.join(ENCRYPTED_FILE);
+    let plaintext = read(&plain_path)?;
+    fs::write(&encrypted_path, encrypt(key, &plaintext))
+        .map_err(|err| InputError::Io(encrypted_path.clone(), err))?;
+
+    // never report success, or delete the plain input, unless the encrypted copy reads
+    // back identically
+    let roundtrip = decrypt(key, &read(&encrypted_path)?, &encrypted_path)?;
+    if roundtrip != plaintext {
+        return Err(InputError::Mismatch(encrypted_path));
+    }
+
+    if remove_plaintext {
+        fs::remove_file(&plain_path).map_err(|err| InputError::Io(plain_path.clone(), err))?;
+    }
+
+    Ok(Some(encrypted_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("input-store-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // the default rounds make unoptimized test builds crawl
+    fn test_key(passphrase: &str) -> InputKey {
+        InputKey::from_passphrase(passphrase).with_rounds(1_000)
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let key = test_key("correct horse");
+        let path = Path::new("data.txt.enc");
+        let encrypted = encrypt(&key, "Game 1: 3 blue, 4 red\n");
+
+        assert!(!encrypted.contains("Game"));
+        assert_ne!(encrypted, encrypt(&key, "Game 1: 3 blue, 4 red\n"));
+        assert_eq!(
+            decrypt(&key, &encrypted, path).unwrap(),
+            "Game 1: 3 blue, 4 red\n"
+        );
+    }
+
+    #[test]
+    fn test_decrypt_errors() {
+        let key = test_key("correct horse");
+        let other_key = test_key("battery staple");
+        let path = Path::new("data.txt.enc");
+        let encrypted = encrypt(&key, "secret");
+
+        assert!(matches!(
+            decrypt(&other_key, &encrypted, path),
+            Err(InputError::WrongKey(_))
+        ));
+        assert!(matches!(
+            decrypt(&key, "not base64!", path),
+            Err(InputError::Corrupted(_))
+        ));
+        assert!(matches!(
+            decrypt(&key, "AAAA", path),
+            Err(InputError::Corrupted(_))
+        ));
+
+        // the rounds are checked before any key is derived from them
+        let mut payload = STANDARD.decode(encrypted.trim()).unwrap();
+        payload[..ROUNDS_LEN].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(matches!(
+            decrypt(&key, &STANDARD.encode(payload), path),
+            Err(InputError::Corrupted(_))
+        ));
+    }
+
+    #[test]
+    fn test_header_stores_salt_and_rounds() {
+        let key = test_key("correct horse");
+        let path = Path::new("data.txt.enc");
+        let first = STANDARD.decode(encrypt(&key, "secret").trim()).unwrap();
+        let second = STANDARD.decode(encrypt(&key, "secret").trim()).unwrap();
+
+        assert_eq!(first[..ROUNDS_LEN], 1_000u32.to_be_bytes());
+        assert_ne!(
+            first[ROUNDS_LEN..ROUNDS_LEN + SALT_LEN],
+            second[ROUNDS_LEN..ROUNDS_LEN + SALT_LEN]
+        );
+
+        // decryption follows the header, not the rounds of the key it is given
+        let encrypted = encrypt(&key.clone().with_rounds(10), "secret");
+        assert_eq!(decrypt(&key, &encrypted, path).unwrap(), "secret");
+    }
+
+    #[test]
+    fn test_load_prefers_encrypted_input() {
+        let dir = temp_dir("load");
+        let key = test_key("correct horse");
+        fs::write(dir.join(ENCRYPTED_FILE), encrypt(&key, "encrypted")).unwrap();
+        fs::write(dir.join(PLAIN_FILE), "plain").unwrap();
+
+        assert_eq!(load_with_key(&dir, Some(&key)).unwrap(), "encrypted");
+        assert_eq!(load_with_key(&dir, None).unwrap(), "plain");
+
+        fs::remove_file(dir.join(PLAIN_FILE)).unwrap();
+        assert!(matches!(
+            load_with_key(&dir, None),
+            Err(InputError::MissingKey(_))
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_missing_input() {
+        let dir = temp_dir("missing");
+
+        assert!(matches!(
+            load_with_key(&dir, None),
+            Err(InputError::MissingInput(_))
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_migrate() {
+        let dir = temp_dir("migrate");
+        let key = test_key("correct horse");
+        fs::write(dir.join(PLAIN_FILE), "1abc2\npqr3stu8vwx\n").unwrap();
+
+        let written = migrate(&dir, &key, true).unwrap();
+
+        assert_eq!(written, Some(dir.join(ENCRYPTED_FILE)));
+        assert!(!dir.join(PLAIN_FILE).exists());
+        assert_eq!(
+            load_with_key(&dir, Some(&key)).unwrap(),
+            "1abc2\npqr3stu8vwx\n"
+        );
+        assert_eq!(migrate(&dir, &key, true).unwrap(), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,9 @@
+pub mod cli;
+pub mod input;
+pub mod input_store;
+pub mod runner;
+pub mod variants;
+
+pub use crate::input::Input;
+pub use crate::runner::Runner;
+pub use crate::variants::Variants;
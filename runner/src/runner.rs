@@ -0,0 +1,174 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process;
+
+use clap::{Args, Parser};
+use visualize::{Frame, Visualizer};
+
+use crate::cli::{self, Cli};
+use crate::input::Input;
+use crate::input_store;
+use crate::variants::Variants;
+
+pub struct Runner {
+    pub cli: Cli,
+    visualizer: Visualizer,
+}
+
+impl Runner {
+    pub fn new(cli: Cli) -> Runner {
+        let visualizer = Visualizer::new(cli.visualize, cli.visualize_dir.clone());
+        Runner { cli, visualizer }
+    }
+
+    pub fn from_args() -> Runner {
+        Runner::new(Cli::parse())
+    }
+
+    // Like from_args, for binaries that take flags of their own besides the shared ones.
+    pub fn from_args_with<A: Args>() -> (Runner, A) {
+        let (cli, args) = cli::parse_with(std::env::args_os()).unwrap_or_else(|err| err.exit());
+        (Runner::new(cli), args)
+    }
+
+    // Reads the puzzle input from `<manifest_dir>/src`, decrypting it when needed,
+    // or from the file given with `--input`.
+    // Call it with `env!("CARGO_MANIFEST_DIR")` from a day's binary.
+    pub fn load_input(&self, manifest_dir: &str) -> String {
+        let loaded = match &self.cli.input {
+            Some(path) if path.as_os_str() == "-" => io::read_to_string(io::stdin()),
+            Some(path) => fs::read_to_string(path),
+            None => input_store::load(&Path::new(manifest_dir).join("src"))
+                .map_err(io::Error::other),
+        };
+
+        loaded.unwrap_or_else(|err| {
+            eprintln!("Could not load puzzle input: {}", err);
+            process::exit(1);
+        })
+    }
+
+    // Like load_input, but a file given with `--input` is memory-mapped and stdin is
+    // read as it comes, so line-oriented solvers never hold the whole input.
+    pub fn open_input(&self, manifest_dir: &str) -> Input {
+        match &self.cli.input {
+            Some(path) if path.as_os_str() == "-" => Input::from_reader(io::stdin().lock()),
+            Some(path) => Input::open(path).unwrap_or_else(|err| {
+                eprintln!("Could not open {}: {}", path.display(), err);
+                process::exit(1);
+            }),
+            None => Input::from_string(self.load_input(manifest_dir)),
+        }
+    }
+
+    // Runs every registered variant, prints how long each one took and returns the
+    // answer. Exits with an error when the variants don't agree.
+    pub fn solve<T: PartialEq + fmt::Display>(&self, variants: Variants<T>) -> T {
+        if variants.is_empty() {
+            panic!("No solvers registered for {}", variants.part);
+        }
+
+        match variants.run() {
+            Ok(report) => {
+                print!("{}", report);
+                report.into_answer().unwrap()
+            }
+            Err(report) => {
+                eprint!("{}", report);
+                eprintln!("Solver variants for {} disagree!", report.part);
+                process::exit(1);
+            }
+        }
+    }
+
+    pub fn is_visualizing(&self) -> bool {
+        self.visualizer.is_enabled()
+    }
+
+    // The frame is only built when a visualization output was requested,
+    // so solvers can call this unconditionally.
+    pub fn visualize<F>(&mut self, name: &str, build_frame: F)
+    where
+        F: FnOnce() -> Frame,
+    {
+        if !self.visualizer.is_enabled() {
+            return;
+        }
+
+        let frame = build_frame();
+        match self.visualizer.show(name, &frame) {
+            Ok(Some(path)) => println!("Wrote visualization to {}", path.display()),
+            Ok(None) => {}
+            Err(err) => eprintln!("Could not write visualization {}: {}", name, err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_visualize_flags() {
+        let cli = Cli::parse_from(["part1", "--visualize", "--visualize-dir", "out"]);
+
+        assert!(cli.visualize);
+        assert_eq!(cli.visualize_dir, Some("out".into()));
+    }
+
+    #[derive(Args, Debug)]
+    struct DayArgs {
+        #[arg(long, default_value = "english")]
+        language: String,
+    }
+
+    #[test]
+    fn test_parse_day_flags() {
+        let (cli, args): (Cli, DayArgs) =
+            cli::parse_with(["part2", "--language", "german", "--visualize"]).unwrap();
+
+        assert!(cli.visualize);
+        assert_eq!(args.language, "german");
+
+        let (_, args): (Cli, DayArgs) = cli::parse_with(["part2"]).unwrap();
+        assert_eq!(args.language, "english");
+        assert!(cli::parse_with::<DayArgs, _, _>(["part2", "--unknown"]).is_err());
+    }
+
+    #[test]
+    fn test_input_flag_overrides_stored_input() {
+        let path = std::env::temp_dir().join(format!("runner-input-{}.txt", process::id()));
+        fs::write(&path, "Card 1: 41 48 | 83 86\n").unwrap();
+
+        let runner = Runner::new(Cli::parse_from([
+            "part1",
+            "--input",
+            path.to_str().unwrap(),
+        ]));
+        let mut input = runner.open_input("/nonexistent");
+
+        assert_eq!(runner.load_input("/nonexistent"), "Card 1: 41 48 | 83 86\n");
+        assert_eq!(
+            input.lines().next().unwrap().unwrap(),
+            "Card 1: 41 48 | 83 86"
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_frames_are_built_lazily() {
+        let mut runner = Runner::new(Cli::default());
+        let mut built = false;
+
+        runner.visualize("unused", || {
+            built = true;
+            Frame::new("unused")
+        });
+
+        assert!(!runner.is_visualizing());
+        assert!(!built);
+    }
+}
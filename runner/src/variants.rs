@@ -0,0 +1,164 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub struct VariantResult<T> {
+    pub name: String,
+    pub answer: T,
+    pub elapsed: Duration,
+}
+
+// Every implementation registered for one part of a puzzle. They are all run
+// and must produce the same answer.
+pub struct Variants<'a, T> {
+    pub part: String,
+    solvers: Vec<(String, Box<dyn FnOnce() -> T + 'a>)>,
+}
+
+impl<'a, T: PartialEq + fmt::Display> Variants<'a, T> {
+    pub fn new(part: &str) -> Variants<'a, T> {
+        Variants {
+            part: part.to_string(),
+            solvers: Vec::new(),
+        }
+    }
+
+    pub fn add<F>(mut self, name: &str, solver: F) -> Variants<'a, T>
+    where
+        F: FnOnce() -> T + 'a,
+    {
+        self.solvers.push((name.to_string(), Box::new(solver)));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.solvers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solvers.is_empty()
+    }
+
+    pub fn run(self) -> Result<PartReport<T>, PartReport<T>> {
+        let results: Vec<VariantResult<T>> = self
+            .solvers
+            .into_iter()
+            .map(|(name, solver)| {
+                let start = Instant::now();
+                let answer = solver();
+                VariantResult {
+                    name,
+                    answer,
+                    elapsed: start.elapsed(),
+                }
+            })
+            .collect();
+
+        let report = PartReport {
+            part: self.part,
+            results,
+        };
+
+        if report.agrees() {
+            Ok(report)
+        } else {
+            Err(report)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PartReport<T> {
+    pub part: String,
+    pub results: Vec<VariantResult<T>>,
+}
+
+impl<T: PartialEq + fmt::Display> PartReport<T> {
+    pub fn agrees(&self) -> bool {
+        self.results
+            .windows(2)
+            .all(|pair| pair[0].answer == pair[1].answer)
+    }
+
+    pub fn answer(&self) -> Option<&T> {
+        self.results.first().map(|result| &result.answer)
+    }
+
+    pub fn into_answer(self) -> Option<T> {
+        self.results.into_iter().next().map(|result| result.answer)
+    }
+}
+
+impl<T: PartialEq + fmt::Display> fmt::Display for PartReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name_width = self
+            .results
+            .iter()
+            .map(|result| result.name.len())
+            .max()
+            .unwrap_or(0);
+
+        writeln!(f, "{}:", self.part)?;
+        for result in &self.results {
+            writeln!(
+                f,
+                "  {:<width$}  {:>12?}  {}",
+                result.name,
+                result.elapsed,
+                result.answer,
+                width = name_width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variants_agree() {
+        let data = [1, 2, 3, 4];
+        let report = Variants::new("Part 1")
+            .add("sum", || data.iter().sum::<u32>())
+            .add("formula", || {
+                data.len() as u32 * (data.len() as u32 + 1) / 2
+            })
+            .run()
+            .unwrap();
+
+        assert_eq!(report.results.len(), 2);
+        assert_eq!(report.results[0].name, "sum");
+        assert_eq!(report.results[1].name, "formula");
+        assert_eq!(report.answer(), Some(&10));
+    }
+
+    #[test]
+    fn test_variants_disagree() {
+        let report = Variants::new("Part 2")
+            .add("right", || 10)
+            .add("wrong", || 11)
+            .run()
+            .err()
+            .unwrap();
+
+        assert!(!report.agrees());
+
+        let table = report.to_string();
+        assert!(table.starts_with("Part 2:\n"));
+        assert!(table.contains("  right  "));
+        assert!(table.lines().nth(1).unwrap().ends_with(" 10"));
+        assert!(table.lines().nth(2).unwrap().ends_with(" 11"));
+    }
+
+    #[test]
+    fn test_no_variants() {
+        let variants: Variants<u32> = Variants::new("Part 1");
+
+        assert!(variants.is_empty());
+
+        let report = variants.run().unwrap();
+        assert_eq!(report.into_answer(), None);
+    }
+}
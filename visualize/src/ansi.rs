@@ -0,0 +1,93 @@
+use crate::frame::{Color, Frame, Style};
+
+const RESET: &str = "\x1b[0m";
+
+fn color_code(color: Color) -> Option<u8> {
+    match color {
+        Color::Default => None,
+        Color::Red => Some(31),
+        Color::Green => Some(32),
+        Color::Yellow => Some(33),
+        Color::Blue => Some(34),
+        Color::Magenta => Some(35),
+        Color::Cyan => Some(36),
+        Color::Gray => Some(90),
+    }
+}
+
+fn escape_for(style: Style) -> String {
+    let mut codes: Vec<String> = Vec::new();
+    if style.bold {
+        codes.push("1".to_string());
+    }
+    if let Some(code) = color_code(style.color) {
+        codes.push(code.to_string());
+    }
+
+    if codes.is_empty() {
+        return String::new();
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+// Renders the frame with ANSI escape codes, only switching styles when they change
+// so long runs of equally styled cells stay cheap to print.
+pub fn render(frame: &Frame) -> String {
+    let mut output = String::new();
+
+    if !frame.title.is_empty() {
+        output.push_str(&escape_for(Style::plain().bold()));
+        output.push_str(&frame.title);
+        output.push_str(RESET);
+        output.push('\n');
+    }
+
+    for row in &frame.rows {
+        let mut current = Style::plain();
+
+        for cell in row {
+            if cell.style != current {
+                if current != Style::plain() {
+                    output.push_str(RESET);
+                }
+                output.push_str(&escape_for(cell.style));
+                current = cell.style;
+            }
+            output.push(cell.ch);
+        }
+
+        if current != Style::plain() {
+            output.push_str(RESET);
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_plain() {
+        let frame = Frame::from_text("", "abc\ndef");
+
+        assert_eq!(render(&frame), "abc\ndef\n");
+    }
+
+    #[test]
+    fn test_render_styles() {
+        let mut frame = Frame::new("Title");
+        frame.push_spans(&[
+            ("a", Style::plain()),
+            ("bc", Style::color(Color::Green)),
+            ("d", Style::color(Color::Red).bold()),
+        ]);
+
+        assert_eq!(
+            render(&frame),
+            "\x1b[1mTitle\x1b[0m\na\x1b[32mbc\x1b[0m\x1b[1;31md\x1b[0m\n"
+        );
+    }
+}
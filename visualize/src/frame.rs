@@ -0,0 +1,144 @@
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Color {
+    #[default]
+    Default,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub color: Color,
+    pub bold: bool,
+}
+
+impl Style {
+    pub fn plain() -> Style {
+        Style::default()
+    }
+
+    pub fn color(color: Color) -> Style {
+        Style { color, bold: false }
+    }
+
+    pub fn bold(self) -> Style {
+        Style { bold: true, ..self }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub style: Style,
+}
+
+// A frame is a grid of styled characters that every renderer knows how to draw.
+// Rows don't need to have the same length.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Frame {
+    pub title: String,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl Frame {
+    pub fn new(title: &str) -> Frame {
+        Frame {
+            title: title.to_string(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn from_text(title: &str, text: &str) -> Frame {
+        let mut frame = Frame::new(title);
+        for line in text.lines() {
+            frame.push_line(line, Style::plain());
+        }
+        frame
+    }
+
+    pub fn push_line(&mut self, text: &str, style: Style) {
+        self.push_spans(&[(text, style)]);
+    }
+
+    pub fn push_spans(&mut self, spans: &[(&str, Style)]) {
+        let row = spans
+            .iter()
+            .flat_map(|(text, style)| text.chars().map(|ch| Cell { ch, style: *style }))
+            .collect();
+        self.rows.push(row);
+    }
+
+    pub fn style_range(&mut self, row: usize, cols: Range<usize>, style: Style) {
+        if let Some(cells) = self.rows.get_mut(row) {
+            let end = cols.end.min(cells.len());
+            for cell in cells[cols.start.min(end)..end].iter_mut() {
+                cell.style = style;
+            }
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn plain_text(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.ch).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_text() {
+        let frame = Frame::from_text("sample", "ab\ncde\n");
+
+        assert_eq!(frame.title, "sample");
+        assert_eq!(frame.height(), 2);
+        assert_eq!(frame.width(), 3);
+        assert_eq!(frame.plain_text(), "ab\ncde");
+    }
+
+    #[test]
+    fn test_push_spans() {
+        let mut frame = Frame::new("spans");
+        frame.push_spans(&[
+            ("AAA", Style::color(Color::Green)),
+            (" -> ", Style::plain()),
+        ]);
+
+        assert_eq!(frame.rows[0].len(), 7);
+        assert_eq!(frame.rows[0][0].style, Style::color(Color::Green));
+        assert_eq!(frame.rows[0][3].style, Style::plain());
+    }
+
+    #[test]
+    fn test_style_range() {
+        let mut frame = Frame::from_text("range", "..467..");
+        frame.style_range(0, 2..5, Style::color(Color::Red).bold());
+        frame.style_range(0, 5..100, Style::color(Color::Gray));
+        frame.style_range(3, 0..1, Style::color(Color::Gray));
+
+        let styles: Vec<Style> = frame.rows[0].iter().map(|cell| cell.style).collect();
+        assert_eq!(styles[1], Style::plain());
+        assert_eq!(styles[2], Style::color(Color::Red).bold());
+        assert_eq!(styles[4], Style::color(Color::Red).bold());
+        assert_eq!(styles[6], Style::color(Color::Gray));
+    }
+}
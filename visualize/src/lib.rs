@@ -0,0 +1,7 @@
+pub mod ansi;
+pub mod frame;
+pub mod sink;
+pub mod svg;
+
+pub use frame::{Color, Frame, Style};
+pub use sink::Visualizer;
@@ -0,0 +1,105 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::frame::Frame;
+use crate::{ansi, svg};
+
+// Collects the frames a solver produces and sends them to the terminal, to a
+// directory of numbered SVG files, or both. A disabled visualizer drops everything.
+#[derive(Debug, Default)]
+pub struct Visualizer {
+    pub terminal: bool,
+    pub output_dir: Option<PathBuf>,
+    frame_count: usize,
+}
+
+impl Visualizer {
+    pub fn new(terminal: bool, output_dir: Option<PathBuf>) -> Visualizer {
+        Visualizer {
+            terminal,
+            output_dir,
+            frame_count: 0,
+        }
+    }
+
+    pub fn disabled() -> Visualizer {
+        Visualizer::default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.terminal || self.output_dir.is_some()
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    pub fn show(&mut self, name: &str, frame: &Frame) -> io::Result<Option<PathBuf>> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+        self.frame_count += 1;
+
+        if self.terminal {
+            print!("{}", ansi::render(frame));
+        }
+
+        match &self.output_dir {
+            Some(dir) => write_svg(dir, self.frame_count, name, frame).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+fn write_svg(dir: &Path, index: usize, name: &str, frame: &Frame) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+
+    let file_name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let path = dir.join(format!("{:04}-{}.svg", index, file_name));
+    fs::write(&path, svg::render(frame))?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_visualizer() {
+        let mut visualizer = Visualizer::disabled();
+        let frame = Frame::from_text("nothing", "...");
+
+        assert!(!visualizer.is_enabled());
+        assert_eq!(visualizer.show("nothing", &frame).unwrap(), None);
+        assert_eq!(visualizer.frame_count(), 0);
+    }
+
+    #[test]
+    fn test_writes_numbered_svg_files() {
+        let dir = std::env::temp_dir().join(format!("visualize-sink-{}", std::process::id()));
+        let mut visualizer = Visualizer::new(false, Some(dir.clone()));
+        let frame = Frame::from_text("route", "AAA -> ZZZ");
+
+        let first = visualizer.show("route", &frame).unwrap().unwrap();
+        let second = visualizer.show("day 8/route", &frame).unwrap().unwrap();
+
+        assert_eq!(first, dir.join("0001-route.svg"));
+        assert_eq!(second, dir.join("0002-day_8_route.svg"));
+        assert!(fs::read_to_string(&first)
+            .unwrap()
+            .contains("AAA -&gt; ZZZ"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
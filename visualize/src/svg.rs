@@ -0,0 +1,152 @@
+use crate::frame::{Cell, Color, Frame, Style};
+
+const CELL_WIDTH: usize = 9;
+const CELL_HEIGHT: usize = 18;
+const FONT_SIZE: usize = 15;
+const PADDING: usize = 10;
+const BACKGROUND: &str = "#1e1e1e";
+
+fn fill_for(color: Color) -> &'static str {
+    match color {
+        Color::Default => "#d4d4d4",
+        Color::Red => "#f14c4c",
+        Color::Green => "#23d18b",
+        Color::Yellow => "#f5f543",
+        Color::Blue => "#3b8eea",
+        Color::Magenta => "#d670d6",
+        Color::Cyan => "#29b8db",
+        Color::Gray => "#6e6e6e",
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn tspan(text: &str, style: Style) -> String {
+    let weight = if style.bold {
+        " font-weight=\"bold\""
+    } else {
+        ""
+    };
+    format!(
+        "<tspan fill=\"{}\"{}>{}</tspan>",
+        fill_for(style.color),
+        weight,
+        escape_xml(text)
+    )
+}
+
+// groups consecutive cells with the same style so each run becomes one <tspan>
+fn style_runs(row: &[Cell]) -> Vec<(String, Style)> {
+    let mut runs: Vec<(String, Style)> = Vec::new();
+
+    for cell in row {
+        match runs.last_mut() {
+            Some((text, style)) if *style == cell.style => text.push(cell.ch),
+            _ => runs.push((cell.ch.to_string(), cell.style)),
+        }
+    }
+    runs
+}
+
+// Writes the frame as a standalone SVG document using a monospace text grid,
+// so it can be opened in any browser without rasterizing anything.
+pub fn render(frame: &Frame) -> String {
+    let title_rows = if frame.title.is_empty() { 0 } else { 1 };
+    let width = frame.width().max(frame.title.chars().count()) * CELL_WIDTH + 2 * PADDING;
+    let height = (frame.height() + title_rows) * CELL_HEIGHT + 2 * PADDING;
+
+    let mut lines: Vec<String> = vec![
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">"
+        ),
+        format!("<rect width=\"100%\" height=\"100%\" fill=\"{BACKGROUND}\"/>"),
+        format!(
+            "<g font-family=\"monospace\" font-size=\"{FONT_SIZE}\" xml:space=\"preserve\">"
+        ),
+    ];
+
+    let mut y = PADDING + CELL_HEIGHT;
+    if title_rows > 0 {
+        lines.push(format!(
+            "<text x=\"{PADDING}\" y=\"{y}\">{}</text>",
+            tspan(&frame.title, Style::plain().bold())
+        ));
+        y += CELL_HEIGHT;
+    }
+
+    for row in &frame.rows {
+        let spans: String = style_runs(row)
+            .iter()
+            .map(|(text, style)| tspan(text, *style))
+            .collect();
+        lines.push(format!("<text x=\"{PADDING}\" y=\"{y}\">{spans}</text>"));
+        y += CELL_HEIGHT;
+    }
+
+    lines.push("</g>".to_string());
+    lines.push("</svg>".to_string());
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("a<b & c>\"d\""),
+            "a&lt;b &amp; c&gt;&quot;d&quot;"
+        );
+    }
+
+    #[test]
+    fn test_style_runs() {
+        let mut frame = Frame::from_text("", "..467..");
+        frame.style_range(0, 2..5, Style::color(Color::Green));
+
+        let runs = style_runs(&frame.rows[0]);
+
+        assert_eq!(
+            runs,
+            vec![
+                ("..".to_string(), Style::plain()),
+                ("467".to_string(), Style::color(Color::Green)),
+                ("..".to_string(), Style::plain()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let mut frame = Frame::new("A&B");
+        frame.push_spans(&[
+            ("x", Style::plain()),
+            ("<y>", Style::color(Color::Red).bold()),
+        ]);
+
+        let svg = render(&frame);
+
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"56\" height=\"56\"")
+        );
+        assert!(svg.contains("<tspan fill=\"#d4d4d4\" font-weight=\"bold\">A&amp;B</tspan>"));
+        assert!(svg.contains(
+            "<text x=\"10\" y=\"46\"><tspan fill=\"#d4d4d4\">x</tspan><tspan fill=\"#f14c4c\" font-weight=\"bold\">&lt;y&gt;</tspan></text>"
+        ));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}